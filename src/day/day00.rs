@@ -1,39 +1,20 @@
-type Prepared = Vec<u64>;
+type Prepared<'input> = Vec<&'input str>;
 
-fn prepare(input: &str) -> Prepared {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn prepare(input: &str) -> Prepared<'_> {
+    input.lines().collect()
 }
 
-fn p1(input: &Prepared) -> u64 {
-    input.iter().sum()
+fn p1(input: &Prepared) -> usize {
+    input.len()
 }
 
-fn p2(input: &Prepared) -> u64 {
-    input.iter().sum()
+fn p2(input: &Prepared) -> usize {
+    input.len()
 }
 
-crate::register!(SOLVER, 0, |ctx, input| {
-    let input = ctx.measure("prepare", || prepare(input));
-    (
-        ctx.measure("part1", || p1(&input)),
-        ctx.measure("part2", || p2(&input)),
-    )
-        .into()
-});
+crate::register!(SOLVER, 0, prepare: prepare, part1: p1, part2: p2);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+const EXAMPLE_INPUT: &str = "";
 
-    const EXAMPLE_INPUT: &str = "";
-
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(&prepare(EXAMPLE_INPUT)), 0);
-    }
-
-    #[test]
-    fn example_part2() {
-        assert_eq!(p2(&prepare(EXAMPLE_INPUT)), 0);
-    }
-}
+// Add `part1: ...` and `part2: ...` once the answers of the example are known.
+crate::register_example!(EXAMPLE, 0, input: EXAMPLE_INPUT);
@@ -67,11 +67,7 @@ crate::register!(SOLVER, 1, |ctx, input| {
     ctx.measure("all", || both(prepare(input))).into()
 });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "L68
+const EXAMPLE_INPUT: &str = "L68
 L30
 R48
 L5
@@ -82,21 +78,4 @@ L99
 R14
 L82";
 
-    fn p1(input: impl IntoIterator<Item = i16>) -> u32 {
-        both(input).0
-    }
-
-    fn p2(input: impl IntoIterator<Item = i16>) -> u32 {
-        both(input).1
-    }
-
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(prepare(EXAMPLE_INPUT)), 3);
-    }
-
-    #[test]
-    fn example_part2() {
-        assert_eq!(p2(prepare(EXAMPLE_INPUT)), 6);
-    }
-}
+crate::register_example!(EXAMPLE, 1, input: EXAMPLE_INPUT, part1: 3, part2: 6);
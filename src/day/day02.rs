@@ -1,3 +1,4 @@
+use crate::infra::error::SolveError;
 use std::ops::RangeInclusive;
 use winnow::Parser;
 use winnow::ascii::dec_uint;
@@ -5,21 +6,40 @@ use winnow::combinator::separated_pair;
 
 type Prepared = Vec<RangeInclusive<u64>>;
 
-fn prepare(input: &str) -> Prepared {
-    input
+/// Number of digits minus one, also for 0.
+fn log10(num: u64) -> u32 {
+    num.checked_ilog10().unwrap_or(0)
+}
+
+fn prepare(input: &str) -> Result<Prepared, SolveError> {
+    let ranges = input
         .trim()
         .split(',')
         .map(|l| {
-            separated_pair(dec_uint::<_, u64, ()>, '-', dec_uint)
+            let range = separated_pair(dec_uint::<_, u64, ()>, '-', dec_uint)
                 .map(|(a, b)| a..=b)
                 .parse(l)
-                .unwrap()
+                .map_err(|e| {
+                    SolveError::parse_at(input, &l[e.offset()..], "expected a range like `11-22`")
+                })?;
+            if range.is_empty() {
+                return Err(SolveError::parse_at(
+                    input,
+                    l,
+                    "expected the range to end after its start",
+                ));
+            }
+            Ok(range)
         })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ranges
+        .into_iter()
         // Make sure each range entry has the same number of digits as start and end.
         .flat_map(|range| {
             let mut next_start = *range.start();
             let mut ranges = vec![];
-            for log in range.start().ilog10()..range.end().ilog10() {
+            for log in log10(*range.start())..log10(*range.end()) {
                 let boundary = 10u64.pow(log + 1);
                 ranges.push(next_start..=(boundary - 1));
                 next_start = boundary;
@@ -27,19 +47,13 @@ fn prepare(input: &str) -> Prepared {
             ranges.push(next_start..=*range.end());
             ranges
         })
-        // Assert previous step.
-        .inspect(|range| {
-            let a = range.start().ilog10();
-            let b = range.end().ilog10();
-            assert_eq!(a, b);
-        })
-        .collect()
+        .collect())
 }
 
 fn p1(input: &Prepared) -> u64 {
     /// Check if the range is entirely valid by checking if the length is odd.
     fn range_valid(range: &RangeInclusive<u64>) -> bool {
-        range.start().ilog10() % 2 == 0
+        log10(*range.start()).is_multiple_of(2)
     }
 
     fn valid(num: u64, factor: u64) -> bool {
@@ -54,8 +68,8 @@ fn p1(input: &Prepared) -> u64 {
         .iter()
         .filter(|range| !range_valid(range))
         .flat_map(|range| {
-            let log = range.start().ilog10();
-            let factor = 10u64.pow((log + 1) / 2);
+            let log = log10(*range.start());
+            let factor = 10u64.pow(log.div_ceil(2));
             range
                 .clone()
                 .into_iter()
@@ -66,10 +80,10 @@ fn p1(input: &Prepared) -> u64 {
 
 fn p2(input: &Prepared) -> u64 {
     fn valid(num: u64) -> bool {
-        let len = num.ilog10() as usize + 1;
+        let len = log10(num) as usize + 1;
 
         'next_slice_length: for slice_length in 1..=len / 2 {
-            if len % slice_length != 0 {
+            if !len.is_multiple_of(slice_length) {
                 // odd length, can't be numbers repeated with length slice_length.
                 continue;
             }
@@ -92,33 +106,38 @@ fn p2(input: &Prepared) -> u64 {
 
     input
         .iter()
-        .flat_map(|range| range.clone().into_iter())
+        .flat_map(|range| range.clone())
         .filter(|num| !valid(*num))
         .sum()
 }
 
-crate::register!(SOLVER, 2, |ctx, input| {
-    let input = ctx.measure("prepare", || prepare(input));
-    (
-        ctx.measure("part1", || p1(&input)),
-        ctx.measure("part2", || p2(&input)),
-    )
-        .into()
-});
+crate::register!(SOLVER, 2, try prepare: prepare, part1: p1, part2: p2);
+
+const EXAMPLE_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+crate::register_example!(
+    EXAMPLE,
+    2,
+    input: EXAMPLE_INPUT,
+    part1: 1227775554,
+    part2: 4174379265,
+);
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(&prepare(EXAMPLE_INPUT)), 1227775554);
-    }
-
     #[test]
-    fn example_part2() {
-        assert_eq!(p2(&prepare(EXAMPLE_INPUT)), 4174379265);
+    fn invalid() {
+        assert!(matches!(
+            prepare("11-22,95-x"),
+            Err(SolveError::Parse { column: 10, .. })
+        ));
+        assert!(matches!(
+            prepare("11-22,100-5"),
+            Err(SolveError::Parse { column: 7, .. })
+        ));
+        assert_eq!(p1(&prepare("0-5,0-0").unwrap()), 0);
+        assert_eq!(p2(&prepare("0-5,0-0").unwrap()), 0);
     }
 }
@@ -1,9 +1,26 @@
+use crate::infra::error::SolveError;
+
 type Prepared = Vec<Vec<u8>>;
 
-fn prepare(input: &str) -> Prepared {
+/// Batteries turned on per bank in part 2, every bank needs at least this many.
+const BATTERIES: usize = 12;
+
+fn prepare(input: &str) -> Result<Prepared, SolveError> {
     input
         .lines()
-        .map(|l| l.as_bytes().iter().map(|c| *c - b'0').collect())
+        .map(|l| {
+            if let Some(pos) = l.bytes().position(|c| !c.is_ascii_digit()) {
+                return Err(SolveError::parse_at(input, &l[pos..], "expected a digit"));
+            }
+            if l.len() < BATTERIES {
+                return Err(SolveError::parse_at(
+                    input,
+                    l,
+                    format!("expected at least {} batteries", BATTERIES),
+                ));
+            }
+            Ok(l.as_bytes().iter().map(|c| *c - b'0').collect())
+        })
         .collect()
 }
 
@@ -32,31 +49,44 @@ fn solve<const N: usize>(input: &Prepared) -> u64 {
         .sum()
 }
 
-crate::register!(SOLVER, 3, |ctx, input| {
-    let input = ctx.measure("prepare", || prepare(input));
-    (
-        ctx.measure("part1", || solve::<2>(&input)),
-        ctx.measure("part2", || solve::<12>(&input)),
-    )
-        .into()
-});
+crate::register!(
+    SOLVER,
+    3,
+    try prepare: prepare,
+    part1: solve::<2>,
+    part2: solve::<BATTERIES>,
+);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "987654321111111
+const EXAMPLE_INPUT: &str = "987654321111111
 811111111111119
 234234234234278
 818181911112111";
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(solve::<2>(&prepare(EXAMPLE_INPUT)), 357);
-    }
+crate::register_example!(
+    EXAMPLE,
+    3,
+    input: EXAMPLE_INPUT,
+    part1: 357,
+    part2: 3121910778619,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(solve::<12>(&prepare(EXAMPLE_INPUT)), 3121910778619);
+    fn invalid() {
+        assert!(matches!(
+            prepare("987654321111111\n8111x1111111119"),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 5,
+                ..
+            })
+        ));
+        assert!(matches!(
+            prepare("987654321111111\n81111"),
+            Err(SolveError::Parse { line: 2, .. })
+        ));
     }
 }
@@ -1,12 +1,33 @@
+use crate::infra::error::SolveError;
 use crate::util::grid::Grid;
-use std::iter::{once, repeat};
+use std::iter::{once, repeat_n};
 
 type Prepared = Grid<u8>;
 
-fn prepare(input: &str) -> Prepared {
-    let width = input.lines().next().unwrap().as_bytes().len();
-    let empty_row = repeat(0).take(width + 2).collect::<Vec<_>>();
-    Grid::from_rows(
+fn prepare(input: &str) -> Result<Prepared, SolveError> {
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 {
+        return Err(SolveError::parse(input, 0, "expected a row of `@` and `.`"));
+    }
+    for line in input.lines() {
+        if line.len() != width {
+            return Err(SolveError::parse_at(
+                input,
+                line,
+                "expected all rows to have the same width",
+            ));
+        }
+        if let Some(pos) = line.bytes().position(|c| c != b'@' && c != b'.') {
+            return Err(SolveError::parse_at(
+                input,
+                &line[pos..],
+                "expected `@` or `.`",
+            ));
+        }
+    }
+
+    let empty_row = repeat_n(0, width + 2).collect::<Vec<_>>();
+    Ok(Grid::from_rows(
         once(empty_row.clone())
             .chain(input.lines().map(|line| {
                 once(0)
@@ -19,7 +40,7 @@ fn prepare(input: &str) -> Prepared {
                     .collect::<Vec<_>>()
             }))
             .chain(once(empty_row)),
-    )
+    ))
 }
 
 fn p1(input: &Prepared) -> u64 {
@@ -30,10 +51,7 @@ fn p1(input: &Prepared) -> u64 {
 }
 
 fn p2(input: &Prepared) -> u64 {
-    let grid = input
-        .rows()
-        .map(|row| row.iter().copied().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let grid = input.rows().map(|row| row.to_vec()).collect::<Vec<_>>();
 
     let mut counts = grid.clone();
     for j in 1..grid.len() - 1 {
@@ -74,20 +92,9 @@ fn p2(input: &Prepared) -> u64 {
     removed
 }
 
-crate::register!(SOLVER, 4, |ctx, input| {
-    let input = ctx.measure("prepare", || prepare(input));
-    (
-        ctx.measure("part1", || p1(&input)),
-        ctx.measure("part2", || p2(&input)),
-    )
-        .into()
-});
+crate::register!(SOLVER, 4, try prepare: prepare, part1: p1, part2: p2);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "..@@.@@@@.
+const EXAMPLE_INPUT: &str = "..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
 @.@@@@..@.
@@ -98,13 +105,26 @@ mod tests {
 .@@@@@@@@.
 @.@.@@@.@.";
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(&prepare(EXAMPLE_INPUT)), 13);
-    }
+crate::register_example!(EXAMPLE, 4, input: EXAMPLE_INPUT, part1: 13, part2: 43);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(p2(&prepare(EXAMPLE_INPUT)), 43);
+    fn invalid() {
+        assert!(prepare("").is_err());
+        assert!(matches!(
+            prepare("..@\n.@"),
+            Err(SolveError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            prepare("..@\n.x@"),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
     }
 }
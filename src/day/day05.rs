@@ -1,3 +1,4 @@
+use crate::infra::error::SolveError;
 use std::cmp::Reverse;
 use std::ops::Range;
 
@@ -6,15 +7,36 @@ struct Prepared {
     ingredients: Vec<u64>,
 }
 
-fn parse(input: &str) -> Prepared {
-    let input = input.as_bytes();
+fn parse(text: &str) -> Result<Prepared, SolveError> {
+    let input = text.as_bytes();
+    let number = |i: usize| {
+        atoi_simd::parse_prefix_pos::<u64>(&input[i.min(input.len())..])
+            .map_err(|e| SolveError::parse(text, i, format!("expected a number: {}", e)))
+    };
+    // The separator after a number, the last line may end without a newline.
+    let separator = |i: usize, expected: u8| match input.get(i) {
+        Some(c) if *c == expected => Ok(()),
+        None if expected == b'\n' => Ok(()),
+        _ => Err(SolveError::parse(
+            text,
+            i,
+            format!("expected `{}`", expected.escape_ascii()),
+        )),
+    };
 
     let mut i = 0;
     let mut fresh = vec![];
-    while input[i] != b'\n' {
-        let (start, len) = atoi_simd::parse_prefix_pos(&input[i..]).unwrap();
+    loop {
+        match input.get(i) {
+            Some(b'\n') => break,
+            Some(_) => {}
+            None => return Err(SolveError::parse(text, i, "expected an empty line")),
+        }
+        let (start, len) = number(i)?;
+        separator(i + len, b'-')?;
         i += len + 1;
-        let (end, len) = atoi_simd::parse_prefix_pos::<u64>(&input[i..]).unwrap();
+        let (end, len) = number(i)?;
+        separator(i + len, b'\n')?;
         i += len + 1;
         fresh.push(start..end + 1);
     }
@@ -22,12 +44,13 @@ fn parse(input: &str) -> Prepared {
 
     let mut ingredients = vec![];
     while i < input.len() {
-        let (number, len) = atoi_simd::parse_prefix_pos(&input[i..]).unwrap();
+        let (number, len) = number(i)?;
+        separator(i + len, b'\n')?;
         i += len + 1;
         ingredients.push(number);
     }
 
-    Prepared { fresh, ingredients }
+    Ok(Prepared { fresh, ingredients })
 }
 
 fn optimize(mut fresh: Vec<Range<u64>>) -> Vec<Range<u64>> {
@@ -83,24 +106,20 @@ fn p2(input: &Prepared) -> u64 {
     input.fresh.iter().map(|r| r.end - r.start).sum()
 }
 
-crate::register!(SOLVER, 5, |ctx, input| {
-    let mut input = ctx.measure("parse", || parse(input));
-    let input = ctx.measure("optimize", || {
-        input.fresh = optimize(input.fresh);
-        input
-    });
-    (
+crate::register!(SOLVER, 5, try |ctx, input| {
+    let input = ctx.span("prepare", |ctx| {
+        let mut input = ctx.measure("parse", || parse(input))?;
+        input.fresh = ctx.measure("optimize", || optimize(input.fresh));
+        Ok(input)
+    })?;
+    Ok((
         ctx.measure("part1", || p1(&input)),
         ctx.measure("part2", || p2(&input)),
     )
-        .into()
+        .into())
 });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "3-5
+const EXAMPLE_INPUT: &str = "3-5
 10-14
 16-20
 12-18
@@ -112,19 +131,27 @@ mod tests {
 17
 32";
 
-    fn prepare(input: &str) -> Prepared {
-        let mut result = parse(input);
-        result.fresh = optimize(result.fresh);
-        result
-    }
+crate::register_example!(EXAMPLE, 5, input: EXAMPLE_INPUT, part1: 3, part2: 14);
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(&prepare(EXAMPLE_INPUT)), 3);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(p2(&prepare(EXAMPLE_INPUT)), 14);
+    fn invalid() {
+        assert!(matches!(
+            parse("3-5\n10-x\n\n1"),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+        assert!(parse("3-5\n").is_err());
+        assert!(matches!(
+            parse("3x5\n\n1"),
+            Err(SolveError::Parse { column: 2, .. })
+        ));
+        assert!(parse("3-5\n\n1,2").is_err());
     }
 }
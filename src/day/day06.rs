@@ -1,3 +1,6 @@
+use crate::infra::error::SolveError;
+use crate::infra::measure::MeasureContext;
+use crate::infra::solver::SolveResult;
 use std::iter::once;
 
 type Prepared<'data, const SIZE: usize> = ([&'data [u8]; SIZE], &'data [u8]);
@@ -32,19 +35,82 @@ fn newline_positions<const CHUNK_SIZE: usize>(input: &[u8]) -> impl Iterator<Ite
         )
 }
 
-fn prepare<const SIZE: usize>(input: &str) -> Prepared<'_, SIZE> {
-    let input = input.as_bytes();
+fn prepare<const SIZE: usize>(text: &str) -> Result<Prepared<'_, SIZE>, SolveError> {
+    let input = text.as_bytes();
 
-    let mut positions = newline_positions::<128>(&input);
+    let mut positions = newline_positions::<128>(input);
     let mut previous_offset = 0;
-    let lines: [&[u8]; SIZE] = std::array::from_fn(|_| {
-        let pos = positions.next().unwrap();
-        let line = &input[previous_offset..pos];
+    let mut lines: [&[u8]; SIZE] = [&[]; SIZE];
+    for line in &mut lines {
+        let pos = positions.next().ok_or_else(|| {
+            SolveError::parse(
+                text,
+                input.len(),
+                format!(
+                    "expected {} rows of numbers followed by the operators",
+                    SIZE
+                ),
+            )
+        })?;
+        *line = &input[previous_offset..pos];
         previous_offset = pos + 1;
-        line
-    });
+    }
+
+    let last_line = &input[previous_offset..input.len()];
+    check(text, &lines, last_line)?;
+    Ok((lines, last_line))
+}
+
+/// Checks what the parts rely on: rows of the same width, an operator in the first column, no
+/// operators beyond the rows and a digit in every column of a problem.
+fn check(text: &str, lines: &[&[u8]], operators: &[u8]) -> Result<(), SolveError> {
+    let offset = |slice: &[u8]| slice.as_ptr() as usize - text.as_ptr() as usize;
+    let width = lines[0].len();
+    if let Some(line) = lines.iter().find(|line| line.len() != width) {
+        return Err(SolveError::parse(
+            text,
+            offset(line),
+            "expected all rows to have the same width",
+        ));
+    }
 
-    (lines, &input[previous_offset..input.len()])
+    let operators = operators.strip_suffix(b"\n").unwrap_or(operators);
+    let is_operator = |c: &u8| *c == b'+' || *c == b'*';
+    if !operators.first().is_some_and(is_operator) {
+        return Err(SolveError::parse(
+            text,
+            offset(operators),
+            "expected an operator in the first column",
+        ));
+    }
+    if let Some(pos) = operators.iter().position(|c| !is_operator(c) && *c != b' ') {
+        return Err(SolveError::parse(
+            text,
+            offset(operators) + pos,
+            "expected `+`, `*` or a space",
+        ));
+    }
+    if operators.len() > width {
+        return Err(SolveError::parse(
+            text,
+            offset(operators) + width,
+            "expected the operators to fit in the rows",
+        ));
+    }
+
+    // The column before an operator separates two problems.
+    let empty = (0..width).find(|col| {
+        !operators.get(col + 1).is_some_and(is_operator)
+            && !lines.iter().any(|line| line[*col].is_ascii_digit())
+    });
+    match empty {
+        Some(col) => Err(SolveError::parse(
+            text,
+            offset(lines[0]) + col,
+            "expected a digit in every column of a problem",
+        )),
+        None => Ok(()),
+    }
 }
 
 fn number(input: &[u8], mut i: usize) -> u64 {
@@ -74,14 +140,11 @@ fn p1<const SIZE: usize>((lines, last_line): &Prepared<'_, SIZE>) -> u64 {
             .map(|idx| col + idx)
             .unwrap_or(last_line.len());
 
-        let col_numbers = lines.iter().map(|line| {
-            let col_num = number(line, col);
-            col_num
-        });
+        let col_numbers = lines.iter().map(|line| number(line, col));
         let local_result = match local_operator {
             b'+' => col_numbers.sum::<u64>(),
             b'*' => col_numbers.reduce(|acc, col_num| acc * col_num).unwrap(),
-            _ => panic!("found char for operator `{}`", char::from(local_operator)),
+            _ => unreachable!("operators are checked by prepare"),
         };
         total_sum += local_result;
 
@@ -110,7 +173,7 @@ fn p2<const SIZE: usize>((lines, last_line): &Prepared<'_, SIZE>) -> u64 {
             lines
                 .iter()
                 .filter_map(|line| match line[col] {
-                    num if matches!(num, b'0'..=b'9') => Some((num - b'0') as u64),
+                    num if num.is_ascii_digit() => Some((num - b'0') as u64),
                     _ => None,
                 })
                 .reduce(|acc, num| acc * 10 + num)
@@ -120,7 +183,7 @@ fn p2<const SIZE: usize>((lines, last_line): &Prepared<'_, SIZE>) -> u64 {
         let local_result = match local_operator {
             b'+' => col_numbers.sum::<u64>(),
             b'*' => col_numbers.reduce(|acc, col_num| acc * col_num).unwrap(),
-            _ => panic!("found char for operator `{}`", char::from(local_operator)),
+            _ => unreachable!("operators are checked by prepare"),
         };
         total_sum += local_result;
 
@@ -130,31 +193,76 @@ fn p2<const SIZE: usize>((lines, last_line): &Prepared<'_, SIZE>) -> u64 {
     total_sum
 }
 
-crate::register!(SOLVER, 6, |ctx, input| {
-    let input = ctx.measure("prepare", || prepare::<4>(&input));
-    (
-        ctx.measure("part1", || p1::<4>(&input)),
-        ctx.measure("part2", || p2::<4>(&input)),
+fn solve<const SIZE: usize>(ctx: &mut MeasureContext, input: &str) -> SolveResult {
+    let input = ctx.measure("prepare", || prepare::<SIZE>(input))?;
+    Ok((
+        ctx.measure("part1", || p1::<SIZE>(&input)),
+        ctx.measure("part2", || p2::<SIZE>(&input)),
     )
-        .into()
-});
+        .into())
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+crate::register!(SOLVER, 6, try |ctx, input, params| {
+    match params.get("rows", 4usize)? {
+        3 => solve::<3>(ctx, input),
+        4 => solve::<4>(ctx, input),
+        rows => Err(SolveError::Invalid(format!(
+            "unsupported number of rows `{}`",
+            rows
+        ))),
+    }
+});
 
-    const EXAMPLE_INPUT: &str = "123 328  51 64\u{0020}
+const EXAMPLE_INPUT: &str = "123 328  51 64\u{0020}
  45 64  387 23\u{0020}
   6 98  215 314
 *   +   *   +  ";
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1::<3>(&prepare::<3>(EXAMPLE_INPUT)), 4277556);
-    }
+crate::register_example!(
+    EXAMPLE,
+    6,
+    input: EXAMPLE_INPUT,
+    part1: 4277556,
+    part2: 3263827,
+    params: [rows = 3],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(p2::<3>(&prepare::<3>(EXAMPLE_INPUT)), 3263827);
+    fn invalid() {
+        assert!(prepare::<3>("").is_err());
+        assert!(prepare::<3>("1 2\n3 4\n").is_err());
+        assert!(matches!(
+            prepare::<2>("1 2\n3 45\n+ *"),
+            Err(SolveError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            prepare::<2>("1 2\n3 4\n  *"),
+            Err(SolveError::Parse {
+                line: 3,
+                column: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            prepare::<2>("1 2\n3 4\n+ -"),
+            Err(SolveError::Parse {
+                line: 3,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            prepare::<2>("1  2\n3  4\n+  *"),
+            Err(SolveError::Parse {
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
+        assert!(prepare::<2>("1 2\n3 4\n+ *\n").is_ok());
     }
 }
@@ -1,10 +1,60 @@
 use std::ops::{BitAnd, BitOrAssign, BitXorAssign};
 use std::simd::prelude::*;
 
+use crate::infra::error::SolveError;
+
+/// Narrowest manifold part 1 handles, two rows must fill its 32 lanes.
+const MIN_WIDTH: usize = 15;
+/// Widest manifold part 1 has room for in its beams.
+const MAX_WIDTH: usize = 144;
+/// The parts start below the first splitters, which take this many rows.
+const MIN_ROWS: usize = 6;
+
+/// Checks the shape the parts rely on: rows of the same width, each ending in a newline, and an
+/// even number of them that does not exceed the width.
+fn prepare(input: &str) -> Result<&str, SolveError> {
+    let width = input.find('\n').ok_or_else(|| {
+        SolveError::parse(input, input.len(), "expected rows ending in a newline")
+    })?;
+    if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) {
+        return Err(SolveError::parse(
+            input,
+            0,
+            format!(
+                "expected a width between {} and {}, found {}",
+                MIN_WIDTH, MAX_WIDTH, width
+            ),
+        ));
+    }
+    if let Some(line) = input.split_terminator('\n').find(|l| l.len() != width) {
+        return Err(SolveError::parse_at(
+            input,
+            line,
+            "expected all rows to have the same width",
+        ));
+    }
+    if !input.ends_with('\n') {
+        return Err(SolveError::parse(
+            input,
+            input.len(),
+            "expected the last row to end in a newline",
+        ));
+    }
+    let rows = input.len() / (width + 1);
+    if !rows.is_multiple_of(2) || !(MIN_ROWS..=width + 1).contains(&rows) {
+        return Err(SolveError::Invalid(format!(
+            "expected an even number of rows between {} and {}, found {}",
+            MIN_ROWS,
+            width + 1,
+            rows
+        )));
+    }
+    Ok(input)
+}
+
 fn p1(input: &str) -> u32 {
     // lane count of 64 is fastest but breaks for the example.
     const LANE_COUNT: usize = 32;
-    const MAX_WIDTH: usize = 144;
 
     let input = input.as_bytes();
     let width = input.iter().position(|c| *c == b'\n').unwrap();
@@ -28,7 +78,7 @@ fn p1(input: &str) -> u32 {
 
         let mut eq_chunks = [0u64; MAX_WIDTH.div_ceil(LANE_COUNT)];
 
-        for (chunk_idx, chunk) in chunks.into_iter().enumerate() {
+        for (chunk_idx, chunk) in chunks.iter().enumerate() {
             let simd_row = Simd::<u8, LANE_COUNT>::from_array(*chunk);
             let eq = simd_row.simd_eq(splitter).to_bitmask();
             let eq = eq.bitand(beams[chunk_idx]);
@@ -67,7 +117,7 @@ fn p2(input: &str) -> u64 {
         let chunk_offset = (j + 1) / LANE_COUNT;
 
         for (chunk_idx, chunk) in chunks
-            .into_iter()
+            .iter()
             .enumerate()
             .take(chunks.len() - chunk_offset)
             .skip(chunk_offset)
@@ -82,7 +132,7 @@ fn p2(input: &str) -> u64 {
             );
 
             let simd_row = Simd::<u8, LANE_COUNT>::from_array(*chunk);
-            let eq = simd_row.simd_eq(splitter).cast();
+            let eq: Mask<i64, LANE_COUNT> = simd_row.simd_eq(splitter).cast();
             cache[chunk_idx] = eq.select(summed, cache[chunk_idx]);
         }
     }
@@ -92,19 +142,9 @@ fn p2(input: &str) -> u64 {
         + cache[(width / 2 + 2) / LANE_COUNT][(width / 2 + 2) % LANE_COUNT]
 }
 
-crate::register!(SOLVER, 7, |ctx, input| {
-    (
-        ctx.measure("part1", || p1(&input)),
-        ctx.measure("part2", || p2(&input)),
-    )
-        .into()
-});
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+crate::register!(SOLVER, 7, try prepare: prepare, part1: p1, part2: p2);
 
-    const EXAMPLE_INPUT: &str = ".......S.......
+const EXAMPLE_INPUT: &str = ".......S.......
 ...............
 .......^.......
 ...............
@@ -122,13 +162,23 @@ mod tests {
 ...............
 ";
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(EXAMPLE_INPUT), 21);
-    }
+crate::register_example!(EXAMPLE, 7, input: EXAMPLE_INPUT, part1: 21, part2: 40);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(p2(EXAMPLE_INPUT), 40);
+    fn invalid() {
+        assert!(prepare("").is_err());
+        assert!(prepare(".....\n").is_err());
+        assert!(matches!(
+            prepare(&EXAMPLE_INPUT.replacen(".......^", "......^", 1)),
+            Err(SolveError::Parse { line: 3, .. })
+        ));
+        assert!(prepare(EXAMPLE_INPUT.trim_end()).is_err());
+        assert!(prepare(&EXAMPLE_INPUT[..16 * 15]).is_err());
+        assert!(prepare(&EXAMPLE_INPUT.repeat(2)).is_err());
+        assert!(prepare(EXAMPLE_INPUT).is_ok());
     }
 }
@@ -1,3 +1,4 @@
+use crate::infra::error::SolveError;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
@@ -5,7 +6,7 @@ type Prepared = (Vec<[u32; 3]>, BinaryHeap<(Reverse<u64>, usize, usize)>);
 
 fn dist_squared(a: [u32; 3], b: [u32; 3]) -> u64 {
     a.into_iter()
-        .zip(b.into_iter())
+        .zip(b)
         .map(|(a, b)| {
             let diff = a.abs_diff(b) as u64;
             diff * diff
@@ -13,15 +14,19 @@ fn dist_squared(a: [u32; 3], b: [u32; 3]) -> u64 {
         .sum()
 }
 
-fn parse(input: &str) -> Vec<[u32; 3]> {
+fn parse(input: &str) -> Result<Vec<[u32; 3]>, SolveError> {
     input
         .lines()
         .map(|l| {
             l.split(",")
-                .map(|num| num.parse().unwrap())
-                .collect::<Vec<_>>()
+                .map(|num| {
+                    num.parse().map_err(|e| {
+                        SolveError::parse_at(input, num, format!("invalid coordinate: {}", e))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
                 .try_into()
-                .unwrap()
+                .map_err(|_| SolveError::parse_at(input, l, "expected three coordinates"))
         })
         .collect()
 }
@@ -42,7 +47,10 @@ fn prepare(junctions: Vec<[u32; 3]>) -> Prepared {
     (junctions, connections)
 }
 
-fn both((junctions, connections): Prepared, connection_count: usize) -> (usize, u64) {
+fn both(
+    (junctions, connections): Prepared,
+    connection_count: usize,
+) -> Result<(usize, u64), SolveError> {
     let mut junction_to_circuit = Vec::from_iter(0..junctions.len());
     let mut circuit_to_junctions = Vec::from_iter((0..junctions.len()).map(|i| vec![i]));
     let mut number_of_circuits = junctions.len();
@@ -57,36 +65,43 @@ fn both((junctions, connections): Prepared, connection_count: usize) -> (usize,
             // Different networks, combine.
             number_of_circuits -= 1;
             if number_of_circuits == 1 {
-                return (p1.unwrap(), junctions[a][0] as u64 * junctions[b][0] as u64);
+                let p1 = p1.ok_or_else(|| {
+                    SolveError::Invalid(format!(
+                        "all junctions are connected before {} connections",
+                        connection_count
+                    ))
+                })?;
+                return Ok((p1, junctions[a][0] as u64 * junctions[b][0] as u64));
             }
             for other in &circuit_to_junctions[y] {
                 junction_to_circuit[*other] = x;
             }
             let [x, y] = circuit_to_junctions.get_disjoint_mut([x, y]).unwrap();
-            x.extend(y.drain(..));
+            x.append(y);
         }
         i += 1;
         if i == connection_count {
             let mut counts =
                 Vec::from_iter(circuit_to_junctions.iter().map(|junctions| junctions.len()));
             counts.sort_unstable_by(|a, b| b.cmp(a));
-            p1 = Some(counts[0] * counts[1] * counts[2]);
+            p1 = Some(counts.iter().take(3).product());
         }
     }
-    panic!("No solution found");
+    Err(SolveError::Invalid(
+        "the junctions never form a single circuit".to_string(),
+    ))
 }
 
-crate::register!(SOLVER, 8, |ctx, input| {
-    let parsed = ctx.measure("parse", || parse(input));
+crate::register!(SOLVER, 8, try |ctx, input, params| {
+    let connection_count = params.get("connection_count", 1000)?;
+    let parsed = ctx.measure("parse", || parse(input))?;
     let prepared = ctx.measure("prepare", || prepare(parsed));
-    ctx.measure("both", || both(prepared, 1000)).into()
+    Ok(ctx
+        .measure("both", || both(prepared, connection_count))?
+        .into())
 });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "162,817,812
+const EXAMPLE_INPUT: &str = "162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -107,20 +122,28 @@ mod tests {
 984,92,344
 425,690,689";
 
-    fn p1(prepared: Prepared) -> usize {
-        both(prepared, 10).0
-    }
-    fn p2(prepared: Prepared) -> u64 {
-        both(prepared, 10).1
-    }
+crate::register_example!(
+    EXAMPLE,
+    8,
+    input: EXAMPLE_INPUT,
+    part1: 40,
+    part2: 25272,
+    params: [connection_count = 10],
+);
 
-    #[test]
-    fn example_part1() {
-        assert_eq!(p1(prepare(parse(EXAMPLE_INPUT))), 40);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part2() {
-        assert_eq!(p2(prepare(parse(EXAMPLE_INPUT))), 25272);
+    fn invalid() {
+        assert!(matches!(
+            both(prepare(parse("1,2,3\n4,5,6").unwrap()), 10),
+            Err(SolveError::Invalid(_))
+        ));
+        assert!(matches!(
+            both(prepare(parse("1,2,3").unwrap()), 10),
+            Err(SolveError::Invalid(_))
+        ));
     }
 }
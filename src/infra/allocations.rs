@@ -0,0 +1,161 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Whether the counting allocator is installed, using the `count-allocations` feature.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Allocations made by a thread during a span.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    pub bytes: u64,
+    pub count: u64,
+    /// Highest number of live bytes, relative to the live bytes at the start.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Mean bytes and count over the samples, with the highest peak.
+    pub fn average(samples: &[AllocStats]) -> Self {
+        let n = samples.len().max(1) as u64;
+        AllocStats {
+            bytes: samples.iter().map(|s| s.bytes).sum::<u64>() / n,
+            count: samples.iter().map(|s| s.count).sum::<u64>() / n,
+            peak: samples.iter().map(|s| s.peak).max().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    bytes: u64,
+    count: u64,
+    /// Can become negative when freeing memory allocated by another thread.
+    live: i64,
+    peak: i64,
+}
+
+impl Counters {
+    const ZERO: Counters = Counters {
+        bytes: 0,
+        count: 0,
+        live: 0,
+        peak: 0,
+    };
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // Fails while the thread is being torn down, those allocations are not counted.
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        f(&mut counters);
+        cell.set(counters);
+    });
+}
+
+fn current() -> Counters {
+    COUNTERS.try_with(Cell::get).unwrap_or(Counters::ZERO)
+}
+
+fn allocated(size: usize, live_change: i64) {
+    update(|counters| {
+        counters.bytes += size as u64;
+        counters.count += 1;
+        counters.live += live_change;
+        counters.peak = counters.peak.max(counters.live);
+    });
+}
+
+/// Forwards to the system allocator, counting the allocations per thread.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        allocated(layout.size(), layout.size() as i64);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        allocated(layout.size(), layout.size() as i64);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        update(|counters| counters.live -= layout.size() as i64);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        allocated(new_size, new_size as i64 - layout.size() as i64);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Allocations of the current thread since [`Tracker::start`], supports nesting.
+pub struct Tracker {
+    start: Counters,
+}
+
+impl Tracker {
+    pub fn start() -> Self {
+        let start = current();
+        update(|counters| counters.peak = counters.live);
+        Tracker { start }
+    }
+
+    pub fn finish(self) -> AllocStats {
+        let end = current();
+        // Restore the peak of an enclosing tracker.
+        update(|counters| counters.peak = counters.peak.max(self.start.peak));
+        AllocStats {
+            bytes: end.bytes - self.start.bytes,
+            count: end.count - self.start.count,
+            peak: (end.peak - self.start.live).max(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker() {
+        let outer = Tracker::start();
+        let inner = Tracker::start();
+        let data = std::hint::black_box(vec![0u8; 1000]);
+        drop(data);
+        let inner = inner.finish();
+        let kept = std::hint::black_box(vec![0u8; 100]);
+        let outer = outer.finish();
+        drop(kept);
+
+        if ENABLED {
+            assert_eq!(
+                inner,
+                AllocStats {
+                    bytes: 1000,
+                    count: 1,
+                    peak: 1000
+                }
+            );
+            assert_eq!(
+                outer,
+                AllocStats {
+                    bytes: 1100,
+                    count: 2,
+                    peak: 1000
+                }
+            );
+        } else {
+            assert_eq!(outer, AllocStats::default());
+        }
+    }
+}
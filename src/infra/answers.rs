@@ -0,0 +1,155 @@
+use crate::infra::solution::Solution;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+pub const DEFAULT_ANSWERS_PATH: &str = "./input/answers.toml";
+
+/// Known-good answers, keyed by year, day name and part:
+///
+/// ```toml
+/// [2025.day01]
+/// part1 = 1034
+/// part2 = "6166"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(toml::Table);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verification {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> std::io::Result<Self> {
+        content
+            .parse()
+            .map(Answers)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Looks up the answer by solver name, e.g. `2025/day01`.
+    pub fn get(&self, name: &str, part: u8) -> Option<String> {
+        let (year, day) = name.split_once('/')?;
+        let value = self.0.get(year)?.get(day)?.get(format!("part{}", part))?;
+        Some(answer_text(value))
+    }
+
+    pub fn verify(&self, name: &str, part: u8, solution: &Solution) -> Verification {
+        Verification::new(self.get(name, part), solution)
+    }
+}
+
+fn answer_text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Integer(i) => i.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Answers of a single input, e.g. the `alice.toml` next to a batch input `alice.txt`:
+///
+/// ```toml
+/// part1 = 1034
+/// part2 = "6166"
+/// ```
+///
+/// Missing files have no answers.
+pub fn load_expected(path: impl AsRef<Path>) -> std::io::Result<[Option<String>; 2]> {
+    let table = match std::fs::read_to_string(path) {
+        Ok(content) => content
+            .parse::<toml::Table>()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(e),
+    };
+    Ok(["part1", "part2"].map(|part| table.get(part).map(answer_text)))
+}
+
+impl Verification {
+    pub fn new(expected: Option<String>, solution: &Solution) -> Self {
+        match expected {
+            None => Verification::Missing,
+            Some(expected) if solution.matches(&expected) => Verification::Pass,
+            Some(expected) => Verification::Fail(expected),
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verification::Pass => "pass",
+            Verification::Fail(_) => "fail",
+            Verification::Missing => "missing",
+        }
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Pass => f.write_str("pass"),
+            Verification::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            Verification::Missing => f.write_str("missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::TempDir;
+    use crate::util::grid::Grid;
+
+    const ANSWERS: &str = r#"
+[2025.day01]
+part1 = 3
+part2 = "6"
+"#;
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.verify("2025/day01", 1, &3u32.into()),
+            Verification::Pass
+        );
+        assert_eq!(
+            answers.verify("2025/day01", 2, &7u64.into()),
+            Verification::Fail("6".to_string())
+        );
+        assert_eq!(
+            answers.verify("2025/day02", 1, &3u32.into()),
+            Verification::Missing
+        );
+
+        let drawing = [
+            "#..#.###", "#..#..#.", "####..#.", "#..#..#.", "#..#..#.", "#..#.###",
+        ];
+        let grid = Grid::from_rows(drawing.map(|row| row.bytes().map(|b| b == b'#')));
+        let answers = Answers::parse("[2025.day10]\npart1 = \"HI\"\npart2 = \"IH\"\n").unwrap();
+        assert_eq!(
+            answers.verify("2025/day10", 1, &grid.clone().into()),
+            Verification::Pass
+        );
+        assert_eq!(
+            answers.verify("2025/day10", 2, &grid.into()),
+            Verification::Fail("IH".to_string())
+        );
+    }
+
+    #[test]
+    fn expected() {
+        let directory = TempDir::new("expected");
+        let path = directory.join("input.toml");
+        assert_eq!(load_expected(&path).unwrap(), [None, None]);
+        std::fs::write(&path, "part2 = 6\n").unwrap();
+        assert_eq!(load_expected(&path).unwrap(), [None, Some("6".to_string())]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::infra::report::Report;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const BASELINE_DIRECTORY: &str = "./target/baselines";
+
+/// Median timings per solver and label, stored in nanoseconds:
+///
+/// ```toml
+/// ["2025/day05"]
+/// time = 12345
+/// parse = 6789
+/// ```
+#[derive(Debug, Default)]
+pub struct Baseline(toml::Table);
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub label: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regression: bool,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive when slower.
+    pub fn change(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// File of the baseline in the directory, the name must not leave it.
+fn path(directory: &Path, name: &str) -> std::io::Result<PathBuf> {
+    if name.is_empty() || name.contains(std::path::is_separator) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("invalid baseline name `{}`", name),
+        ));
+    }
+    Ok(directory.join(format!("{}.toml", name)))
+}
+
+fn timings(report: &Report) -> impl Iterator<Item = (&str, Duration)> {
+    [("time", report.duration.median)].into_iter().chain(
+        report
+            .measurements
+            .iter()
+            .map(|(label, stats)| (label.as_str(), stats.median)),
+    )
+}
+
+impl Baseline {
+    pub fn load(name: &str) -> std::io::Result<Self> {
+        Self::load_from(BASELINE_DIRECTORY.as_ref(), name)
+    }
+
+    pub fn load_from(directory: &Path, name: &str) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path(directory, name)?)?;
+        content
+            .parse()
+            .map(Baseline)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Loads the existing baseline so solvers that were not run keep their timings.
+    pub fn load_or_default(name: &str) -> std::io::Result<Self> {
+        match Self::load(name) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            result => result,
+        }
+    }
+
+    pub fn save(&self, name: &str) -> std::io::Result<()> {
+        self.save_to(BASELINE_DIRECTORY.as_ref(), name)
+    }
+
+    pub fn save_to(&self, directory: &Path, name: &str) -> std::io::Result<()> {
+        let path = path(directory, name)?;
+        std::fs::create_dir_all(directory)?;
+        std::fs::write(path, self.0.to_string())
+    }
+
+    pub fn record(&mut self, report: &Report) {
+        let table = timings(report)
+            .map(|(label, duration)| {
+                let nanos = duration.as_nanos().min(i64::MAX as u128) as i64;
+                (label.to_string(), toml::Value::Integer(nanos))
+            })
+            .collect();
+        self.0
+            .insert(report.name.clone(), toml::Value::Table(table));
+    }
+
+    /// Compares the report against the baseline, a slowdown above the threshold percentage is
+    /// flagged as a regression.
+    pub fn compare(&self, report: &Report, threshold: f64) -> Vec<Comparison> {
+        let Some(table) = self.0.get(&report.name) else {
+            return vec![];
+        };
+        timings(report)
+            .filter_map(|(label, current)| {
+                let baseline = table.get(label)?.as_integer()?;
+                let baseline = Duration::from_nanos(baseline.max(1) as u64);
+                let mut comparison = Comparison {
+                    label: label.to_string(),
+                    baseline,
+                    current,
+                    regression: false,
+                };
+                comparison.regression = comparison.change() > threshold;
+                Some(comparison)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::{self, TempDir};
+
+    fn report(time: u64, parse: u64) -> Report {
+        testing::report(time, &[("parse", parse)])
+    }
+
+    #[test]
+    fn round_trip() {
+        let directory = TempDir::new("baseline");
+        let mut baseline = Baseline::default();
+        baseline.record(&report(2000, 1000));
+        baseline.save_to(directory.path(), "main").unwrap();
+
+        let loaded = Baseline::load_from(directory.path(), "main").unwrap();
+        assert_eq!(loaded.0, baseline.0);
+        assert_eq!(loaded.0["2025/day05"]["time"], toml::Value::Integer(2000));
+        assert!(matches!(
+            Baseline::load_from(directory.path(), "other"),
+            Err(e) if e.kind() == ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn regression() {
+        let mut baseline = Baseline::default();
+        baseline.record(&report(2000, 1000));
+
+        let comparisons = baseline.compare(&report(2100, 1500), 10.0);
+        let flagged = comparisons
+            .iter()
+            .map(|c| (c.label.as_str(), c.regression))
+            .collect::<Vec<_>>();
+        assert_eq!(flagged, [("time", false), ("parse", true)]);
+        assert_eq!(comparisons[1].change(), 50.0);
+
+        let mut other = report(2000, 1000);
+        other.name = "2025/day06".to_string();
+        assert!(baseline.compare(&other, 10.0).is_empty());
+    }
+
+    #[test]
+    fn name() {
+        let directory = TempDir::new("baseline-name");
+        for name in ["../../x", "a/b", ""] {
+            assert_eq!(
+                Baseline::default()
+                    .save_to(directory.path(), name)
+                    .unwrap_err()
+                    .kind(),
+                ErrorKind::InvalidInput
+            );
+        }
+        assert!(Baseline::load("../x").is_err());
+    }
+}
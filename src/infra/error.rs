@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter};
+
+/// Failure of a solver on its input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveError {
+    /// The input could not be parsed, line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input was parsed but has no solution.
+    Invalid(String),
+}
+
+impl SolveError {
+    /// Parse error at the byte offset into the input.
+    pub fn parse(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input.as_bytes()[..offset.min(input.len())];
+        let line_start = before
+            .iter()
+            .rposition(|c| *c == b'\n')
+            .map_or(0, |pos| pos + 1);
+        SolveError::Parse {
+            line: before.iter().filter(|c| **c == b'\n').count() + 1,
+            column: before.len() - line_start + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Parse error at the start of `rest`, which must be a subslice of the input.
+    pub fn parse_at(input: &str, rest: &str, message: impl Into<String>) -> Self {
+        let offset = rest.as_ptr() as usize - input.as_ptr() as usize;
+        debug_assert!(offset <= input.len());
+        Self::parse(input, offset, message)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "parse error at {}:{}: {}", line, column, message),
+            SolveError::Invalid(message) => write!(f, "invalid input: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position() {
+        let input = "12\n34,x\n";
+        assert_eq!(
+            SolveError::parse_at(input, &input[6..], "expected a number").to_string(),
+            "parse error at 2:4: expected a number"
+        );
+        assert_eq!(
+            SolveError::parse(input, 0, "x"),
+            SolveError::Parse {
+                line: 1,
+                column: 1,
+                message: "x".to_string()
+            }
+        );
+    }
+}
@@ -1,31 +1,664 @@
 use crate::infra::solver::day_to_name;
+use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const YEAR: u64 = 2025;
-
-pub fn read_input(day: u8) -> String {
-    let filepath = format!("./input/{}.txt", day_to_name(day));
-    std::fs::read_to_string(&filepath).unwrap_or_else(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => {
-            let mut input = ureq::get(format!(
-                "https://adventofcode.com/{}/day/{}/input",
-                YEAR, day
-            ))
-            .header("User-Agent", "https://github.com/pvandommelen/aoc-2025")
-            .header(
-                "Cookie",
-                format!(
-                    "session={}",
-                    std::env::var("AOC_SESSION_TOKEN")
-                        .expect("Missing AOC_SESSION_TOKEN environment variable")
-                ),
-            )
+pub const DEFAULT_INPUT_DIRECTORY: &str = "./input";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Checksums of the downloaded inputs, inside the input directory.
+pub const MANIFEST_FILE: &str = "checksums.toml";
+/// Time of the last download in milliseconds since the Unix epoch, inside the input directory,
+/// so that the request interval also holds between runs.
+pub const LAST_REQUEST_FILE: &str = "last-request";
+/// Minimum time between two requests of a [`HttpProvider`].
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+/// Sent with every request, so the server knows where automated requests come from.
+const USER_AGENT: &str = "https://github.com/pvandommelen/aoc-2025";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(u16, u8),
+    MissingSessionToken,
+    Io(PathBuf, std::io::Error),
+    /// Also covers responses with an error status, which ureq reports as errors.
+    Http(String, ureq::Error),
+    /// The body is not a puzzle input, e.g. an error or login page.
+    Invalid(String, &'static str),
+    /// The input file no longer matches the checksum recorded when it was downloaded.
+    Modified(PathBuf),
+    /// The puzzle unlocks after the duration.
+    Locked(u16, u8, Duration),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(year, day) => {
+                write!(f, "no input for {}/{}", year, day_to_name(*day))
+            }
+            InputError::MissingSessionToken => {
+                f.write_str("missing AOC_SESSION_TOKEN environment variable")
+            }
+            InputError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            InputError::Http(url, e) => write!(f, "{}: {}", url, e),
+            InputError::Invalid(source, reason) => write!(f, "{}: {}", source, reason),
+            InputError::Modified(path) => write!(
+                f,
+                "{} was modified after it was downloaded, use --refresh-input to download it again",
+                path.display()
+            ),
+            InputError::Locked(year, day, remaining) => write!(
+                f,
+                "{}/{} unlocks in {}, use --wait-for-unlock to wait for it",
+                year,
+                day_to_name(*day),
+                format_remaining(*remaining)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Days since the Unix epoch of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on their day of December.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs((days * 86400 + 5 * 3600) as u64)
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    match seconds / 86400 {
+        0 => format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        days => format!(
+            "{}d {:02}:{:02}:{:02}",
+            days,
+            seconds / 3600 % 24,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+    }
+}
+
+/// Checks that the body looks like a puzzle input rather than a page served instead of it.
+pub fn validate_input(body: &str) -> Result<(), &'static str> {
+    let start = body.trim_start();
+    if start.is_empty() {
+        Err("empty input")
+    } else if start.starts_with('<') {
+        Err("received an HTML page, the session token may have expired")
+    } else if start.starts_with("Please don't repeatedly request this endpoint") {
+        Err("the puzzle has not unlocked yet")
+    } else if start.starts_with("Puzzle inputs differ by user") {
+        Err("not logged in, check the session token")
+    } else {
+        Ok(())
+    }
+}
+
+fn checksum(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// SHA-256 checksums of the stored inputs, keyed by year and day name:
+///
+/// ```toml
+/// [2025]
+/// day01 = "9f86d0..."
+/// ```
+#[derive(Debug, Default)]
+struct Manifest(toml::Table);
+
+impl Manifest {
+    fn load(path: &Path) -> Result<Self, InputError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => content.parse().map(Manifest).map_err(|e| {
+                InputError::Io(
+                    path.to_path_buf(),
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+                )
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(InputError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), InputError> {
+        std::fs::write(path, self.0.to_string()).map_err(|e| InputError::Io(path.to_path_buf(), e))
+    }
+
+    fn get(&self, year: u16, day: u8) -> Option<&str> {
+        self.0
+            .get(&year.to_string())?
+            .get(day_to_name(day))?
+            .as_str()
+    }
+
+    fn set(&mut self, year: u16, day: u8, checksum: String) {
+        let year = self
+            .0
+            .entry(year.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if let toml::Value::Table(year) = year {
+            year.insert(day_to_name(day), toml::Value::String(checksum));
+        }
+    }
+}
+
+pub trait InputProvider {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError>;
+}
+
+impl<P: InputProvider + ?Sized> InputProvider for Box<P> {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError> {
+        (**self).input(year, day)
+    }
+}
+
+/// One of the inputs of a day in [`DirectoryProvider::batch`].
+#[derive(Debug)]
+pub struct BatchInput {
+    /// File name without the extension, e.g. `alice` for `alice.txt`.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Reads `YYYY/dayNN.txt` files from a directory. Stored inputs are recorded in the checksum
+/// manifest, reading an input that no longer matches its checksum fails.
+pub struct DirectoryProvider {
+    directory: PathBuf,
+}
+
+impl DirectoryProvider {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        DirectoryProvider {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.directory
+            .join(year.to_string())
+            .join(format!("{}.txt", day_to_name(day)))
+    }
+
+    /// Inputs of a day shared by several accounts, the `*.txt` files in `YYYY/dayNN/` sorted by
+    /// name. Empty when the directory does not exist.
+    pub fn batch(&self, year: u16, day: u8) -> Result<Vec<BatchInput>, InputError> {
+        let directory = self.directory.join(year.to_string()).join(day_to_name(day));
+        let entries = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(InputError::Io(directory, e)),
+        };
+        let mut paths = entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| InputError::Io(directory, e))?;
+        paths.retain(|path| path.extension().is_some_and(|extension| extension == "txt"));
+        paths.sort_unstable();
+        Ok(paths
+            .into_iter()
+            .map(|path| BatchInput {
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                path,
+            })
+            .collect())
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.directory.join(MANIFEST_FILE)
+    }
+
+    pub fn store(&self, year: u16, day: u8, input: &str) -> Result<(), InputError> {
+        let path = self.path(year, day);
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, input))
+            .map_err(|e| InputError::Io(path, e))?;
+
+        let manifest_path = self.manifest_path();
+        let mut manifest = Manifest::load(&manifest_path)?;
+        manifest.set(year, day, checksum(input));
+        manifest.save(&manifest_path)
+    }
+}
+
+impl InputProvider for DirectoryProvider {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let path = self.path(year, day);
+        let input = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => InputError::NotFound(year, day),
+            _ => InputError::Io(path.clone(), e),
+        })?;
+        let manifest = Manifest::load(&self.manifest_path())?;
+        if manifest
+            .get(year, day)
+            .is_some_and(|expected| expected != checksum(&input))
+        {
+            return Err(InputError::Modified(path));
+        }
+        Ok(input)
+    }
+}
+
+/// Downloads the input using the session token, from adventofcode.com or a server mimicking it.
+/// Refuses to request puzzles that have not unlocked yet, and keeps a minimum interval between
+/// requests.
+pub struct HttpProvider {
+    base_url: String,
+    session_token: Option<String>,
+    min_interval: Duration,
+    wait_for_unlock: bool,
+    last_request: Mutex<Option<SystemTime>>,
+    last_request_file: Option<PathBuf>,
+}
+
+impl HttpProvider {
+    pub fn new(base_url: impl Into<String>, session_token: Option<String>) -> Self {
+        HttpProvider {
+            base_url: base_url.into(),
+            session_token,
+            min_interval: DEFAULT_REQUEST_INTERVAL,
+            wait_for_unlock: false,
+            last_request: Mutex::new(None),
+            last_request_file: None,
+        }
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Stores the time of every request in the file and keeps the interval to a request stored
+    /// there by an earlier run.
+    pub fn with_last_request_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.last_request_file = Some(path.into());
+        self
+    }
+
+    /// Waits for locked puzzles with a countdown on stderr, instead of failing.
+    pub fn with_wait_for_unlock(mut self, wait_for_unlock: bool) -> Self {
+        self.wait_for_unlock = wait_for_unlock;
+        self
+    }
+
+    fn wait_until_unlocked(&self, year: u16, day: u8) -> Result<(), InputError> {
+        let unlock = unlock_time(year, day);
+        let mut announced = false;
+        while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+            if !self.wait_for_unlock {
+                return Err(InputError::Locked(year, day, remaining));
+            }
+            eprint!(
+                "\rWaiting for {}/{} to unlock: {} ",
+                year,
+                day_to_name(day),
+                format_remaining(remaining)
+            );
+            announced = true;
+            std::thread::sleep(remaining.min(Duration::from_secs(1)));
+        }
+        if announced {
+            eprintln!();
+        }
+        Ok(())
+    }
+
+    /// Sleeps until the minimum interval since the previous request has passed, also when that
+    /// request was made by an earlier run.
+    fn throttle(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+        let stored = self.last_request_file.as_ref().and_then(|path| {
+            let millis = std::fs::read_to_string(path).ok()?.trim().parse().ok()?;
+            Some(UNIX_EPOCH + Duration::from_millis(millis))
+        });
+        if let Some(last) = (*last_request).max(stored) {
+            // A time in the future counts as just now.
+            let elapsed = last.elapsed().unwrap_or_default();
+            std::thread::sleep(self.min_interval.saturating_sub(elapsed));
+        }
+        let now = SystemTime::now();
+        *last_request = Some(now);
+        if let Some(path) = &self.last_request_file {
+            let millis = now
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            // Only a courtesy to the server, failing to store the time does not stop the download.
+            let _ = std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+                .and_then(|_| std::fs::write(path, millis.to_string()));
+        }
+    }
+
+    /// Uses the `AOC_BASE_URL` and `AOC_SESSION_TOKEN` environment variables.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+            std::env::var("AOC_SESSION_TOKEN").ok(),
+        )
+    }
+}
+
+/// Adds the user agent and the session cookie to a request to the server, `None` without a
+/// session token.
+pub(crate) fn authenticated<B>(
+    request: ureq::RequestBuilder<B>,
+    session_token: Option<&str>,
+) -> Option<ureq::RequestBuilder<B>> {
+    Some(
+        request
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", format!("session={}", session_token?)),
+    )
+}
+
+impl InputProvider for HttpProvider {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let request = authenticated(ureq::get(&url), self.session_token.as_deref())
+            .ok_or(InputError::MissingSessionToken)?;
+        self.wait_until_unlocked(year, day)?;
+        self.throttle();
+        let mut response = request
             .call()
-            .unwrap();
-            let input = input.body_mut().read_to_string().unwrap();
-            let input = input.to_string();
-            std::fs::write(&filepath, &input).unwrap();
-            input
-        }
-        _ => panic!("{}", e),
-    })
+            .map_err(|e| InputError::Http(url.clone(), e))?;
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| InputError::Http(url.clone(), e))?;
+        validate_input(&body).map_err(|reason| InputError::Invalid(url, reason))?;
+        Ok(body)
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryProvider(FxHashMap<(u16, u8), String>);
+
+impl MemoryProvider {
+    pub fn insert(&mut self, year: u16, day: u8, input: impl Into<String>) {
+        self.0.insert((year, day), input.into());
+    }
+}
+
+impl InputProvider for MemoryProvider {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError> {
+        self.0
+            .get(&(year, day))
+            .cloned()
+            .ok_or(InputError::NotFound(year, day))
+    }
+}
+
+/// Reads from the directory, falling back to the remote provider and storing its result. Only
+/// valid inputs are stored, see [`validate_input`].
+pub struct CachedProvider<R> {
+    cache: DirectoryProvider,
+    remote: R,
+    refresh: bool,
+}
+
+impl<R: InputProvider> CachedProvider<R> {
+    pub fn new(cache: DirectoryProvider, remote: R) -> Self {
+        CachedProvider {
+            cache,
+            remote,
+            refresh: false,
+        }
+    }
+
+    /// Always fetches from the remote provider, replacing the stored inputs.
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let input = self.remote.input(year, day)?;
+        validate_input(&input).map_err(|reason| {
+            InputError::Invalid(format!("{}/{}", year, day_to_name(day)), reason)
+        })?;
+        self.cache.store(year, day, &input)?;
+        Ok(input)
+    }
+}
+
+impl<R: InputProvider> InputProvider for CachedProvider<R> {
+    fn input(&self, year: u16, day: u8) -> Result<String, InputError> {
+        if self.refresh {
+            return self.fetch(year, day);
+        }
+        match self.cache.input(year, day) {
+            Err(InputError::NotFound(_, _)) => self.fetch(year, day),
+            result => result,
+        }
+    }
+}
+
+/// Reads an input from a file outside the input directory, or from stdin for `-`.
+pub fn read_input_file(path: &str) -> Result<String, InputError> {
+    if path == "-" {
+        std::io::read_to_string(std::io::stdin())
+            .map_err(|e| InputError::Io(PathBuf::from("<stdin>"), e))
+    } else {
+        std::fs::read_to_string(path).map_err(|e| InputError::Io(PathBuf::from(path), e))
+    }
+}
+
+pub fn read_input(year: u16, day: u8) -> Result<String, InputError> {
+    CachedProvider::new(
+        DirectoryProvider::new(DEFAULT_INPUT_DIRECTORY),
+        HttpProvider::from_env(),
+    )
+    .input(year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::{TempDir, mock_server};
+    use std::time::Instant;
+
+    #[test]
+    fn http_provider() {
+        let (base_url, request) = mock_server("200 OK", "1\n2\n");
+        let provider = HttpProvider::new(base_url, Some("token".to_string()));
+        assert_eq!(provider.input(2024, 5).unwrap(), "1\n2\n");
+
+        let request = request();
+        assert!(request.starts_with("GET /2024/day/5/input "));
+        assert!(request.contains("session=token"));
+    }
+
+    #[test]
+    fn http_provider_error() {
+        let (base_url, request) = mock_server("404 Not Found", "");
+        let provider = HttpProvider::new(base_url, Some("token".to_string()));
+        assert!(matches!(
+            provider.input(2025, 5),
+            Err(InputError::Http(_, _))
+        ));
+        request();
+
+        let provider = HttpProvider::new(DEFAULT_BASE_URL, None);
+        assert!(matches!(
+            provider.input(2025, 5),
+            Err(InputError::MissingSessionToken)
+        ));
+    }
+
+    #[test]
+    fn cached_provider() {
+        let directory = TempDir::new("input");
+
+        let mut remote = MemoryProvider::default();
+        remote.insert(2025, 3, "123");
+        let provider = CachedProvider::new(DirectoryProvider::new(directory.path()), remote);
+        assert_eq!(provider.input(2025, 3).unwrap(), "123");
+        assert!(matches!(
+            provider.input(2025, 4),
+            Err(InputError::NotFound(2025, 4))
+        ));
+        assert_eq!(
+            DirectoryProvider::new(directory.path())
+                .input(2025, 3)
+                .unwrap(),
+            "123"
+        );
+    }
+
+    #[test]
+    fn input_file() {
+        let directory = TempDir::new("input-file");
+        let path = directory.join("input.txt");
+        std::fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!(read_input_file(path.to_str().unwrap()).unwrap(), "1\n2\n");
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            read_input_file(path.to_str().unwrap()),
+            Err(InputError::Io(_, _))
+        ));
+    }
+
+    #[test]
+    fn batch() {
+        let directory = TempDir::new("input-batch");
+        let provider = DirectoryProvider::new(directory.path());
+        assert!(provider.batch(2025, 5).unwrap().is_empty());
+
+        std::fs::create_dir_all(directory.join("2025/day05")).unwrap();
+        for (file, content) in [("bob.txt", "2"), ("alice.txt", "1"), ("alice.toml", "")] {
+            std::fs::write(directory.join("2025/day05").join(file), content).unwrap();
+        }
+        let inputs = provider.batch(2025, 5).unwrap();
+        let inputs = inputs
+            .iter()
+            .map(|batch| (batch.name.as_str(), batch.path.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inputs,
+            [
+                ("alice", directory.join("2025/day05/alice.txt")),
+                ("bob", directory.join("2025/day05/bob.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn unlock() {
+        assert_eq!(
+            unlock_time(2025, 1),
+            UNIX_EPOCH + Duration::from_secs(1764565200)
+        );
+        assert_eq!(
+            unlock_time(2024, 25)
+                .duration_since(unlock_time(2024, 24))
+                .unwrap(),
+            Duration::from_secs(86400)
+        );
+        assert_eq!(format_remaining(Duration::from_secs(3725)), "01:02:05");
+
+        let provider = HttpProvider::new(DEFAULT_BASE_URL, Some("token".to_string()));
+        assert!(matches!(
+            provider.input(2999, 1),
+            Err(InputError::Locked(2999, 1, _))
+        ));
+    }
+
+    #[test]
+    fn throttle() {
+        let provider =
+            HttpProvider::new(DEFAULT_BASE_URL, None).with_min_interval(Duration::from_millis(50));
+        let start = Instant::now();
+        provider.throttle();
+        provider.throttle();
+        provider.throttle();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        let directory = TempDir::new("input-throttle");
+        let path = directory.join(LAST_REQUEST_FILE);
+        let provider = || {
+            HttpProvider::new(DEFAULT_BASE_URL, None)
+                .with_min_interval(Duration::from_millis(200))
+                .with_last_request_file(&path)
+        };
+        provider().throttle();
+        assert!(path.exists());
+        let start = Instant::now();
+        provider().throttle();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate_input("1\n2\n").is_ok());
+        assert!(validate_input("").is_err());
+        assert!(validate_input("<!DOCTYPE html>\n<html>").is_err());
+        assert!(
+            validate_input("Please don't repeatedly request this endpoint before it unlocks!")
+                .is_err()
+        );
+
+        let (base_url, request) = mock_server("200 OK", "<html>login</html>");
+        let provider = HttpProvider::new(base_url, Some("token".to_string()));
+        assert!(matches!(
+            provider.input(2025, 1),
+            Err(InputError::Invalid(_, _))
+        ));
+        request();
+    }
+
+    #[test]
+    fn refresh_and_manifest() {
+        let directory = TempDir::new("input-manifest");
+        let cache = DirectoryProvider::new(directory.path());
+
+        let mut remote = MemoryProvider::default();
+        remote.insert(2025, 1, "new");
+        remote.insert(2025, 2, "");
+        cache.store(2025, 1, "old").unwrap();
+        let provider = CachedProvider::new(DirectoryProvider::new(directory.path()), remote);
+        assert_eq!(provider.input(2025, 1).unwrap(), "old");
+        assert!(matches!(
+            provider.input(2025, 2),
+            Err(InputError::Invalid(_, _))
+        ));
+        assert!(matches!(
+            cache.input(2025, 2),
+            Err(InputError::NotFound(2025, 2))
+        ));
+
+        let provider = provider.with_refresh(true);
+        assert_eq!(provider.input(2025, 1).unwrap(), "new");
+        assert_eq!(cache.input(2025, 1).unwrap(), "new");
+
+        std::fs::write(directory.join("2025/day01.txt"), "edited").unwrap();
+        assert!(matches!(cache.input(2025, 1), Err(InputError::Modified(_))));
+    }
 }
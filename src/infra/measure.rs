@@ -1,52 +1,254 @@
+use crate::infra::allocations::{self, AllocStats, Tracker};
+use crate::infra::perf::{self, PerfCounters, PerfStats};
 use indexmap::IndexMap;
 use std::time::{Duration, Instant};
 
-#[derive(Debug)]
 pub struct MeasureContext {
     start: Instant,
-    measurements: Vec<(&'static str, Instant)>,
+    spans: Vec<Span>,
+    /// Indices of the spans that have started but not yet finished.
+    open: Vec<usize>,
+    /// Start of each iteration, with the index of its first span.
+    iterations: Vec<(Instant, usize)>,
+    /// Only opened with the `perf-counters` feature.
+    counters: Option<PerfCounters>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    label: &'static str,
+    parent: Option<usize>,
+    start: Instant,
+    end: Instant,
+    allocations: AllocStats,
+    counters: PerfStats,
+    /// Left out of the measurements, see [`MeasureContext::discard`].
+    discarded: bool,
+}
+
+/// Spans aggregated by their path of labels, see [`MeasureContext::tree`].
+#[derive(Clone, Debug)]
+pub struct SpanNode {
+    pub label: &'static str,
+    pub depth: usize,
+    /// Duration per iteration, including the children.
+    pub total: Vec<Duration>,
+    /// Duration per iteration not spent in any of the children.
+    pub self_time: Vec<Duration>,
+    /// Allocations per iteration including the children, only counted with the
+    /// `count-allocations` feature.
+    pub allocations: Vec<AllocStats>,
+    /// Hardware counters per iteration including the children, only counted with the
+    /// `perf-counters` feature.
+    pub counters: Vec<PerfStats>,
 }
 
 impl MeasureContext {
     pub fn new() -> Self {
-        MeasureContext {
-            start: Instant::now(),
-            measurements: vec![],
-        }
+        Self::with_capacity(0)
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        let capacity = Vec::with_capacity(capacity);
         MeasureContext {
             start: Instant::now(),
-            measurements: capacity,
+            spans: Vec::with_capacity(capacity),
+            open: vec![],
+            iterations: vec![],
+            counters: perf::ENABLED.then(PerfCounters::open).and_then(Result::ok),
         }
     }
 
+    /// Whether hardware counters are recorded for the spans.
+    pub fn has_counters(&self) -> bool {
+        self.counters.is_some()
+    }
+
     #[inline]
     pub fn measure<T>(&mut self, label: &'static str, f: impl FnOnce() -> T) -> T {
-        let result = f();
-        self.measurements.push((label, Instant::now()));
+        self.span(label, |_| f())
+    }
+
+    /// Like [`MeasureContext::measure`], but passes the context so nested spans can be measured
+    /// as children of this one.
+    #[inline]
+    pub fn span<T>(&mut self, label: &'static str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let index = self.spans.len();
+        let start = Instant::now();
+        self.spans.push(Span {
+            label,
+            parent: self.open.last().copied(),
+            start,
+            end: start,
+            allocations: AllocStats::default(),
+            counters: PerfStats::default(),
+            discarded: false,
+        });
+        self.open.push(index);
+        let tracker = allocations::ENABLED.then(Tracker::start);
+        let counters = self.counters.as_ref().map(PerfCounters::read);
+        let result = f(self);
+        if let (Some(counters), Some(start)) = (&self.counters, counters) {
+            self.spans[index].counters = counters.read().since(&start);
+        }
+        if let Some(tracker) = tracker {
+            self.spans[index].allocations = tracker.finish();
+        }
+        self.open.pop();
+        self.spans[index].end = Instant::now();
         result
     }
 
+    /// Marks the start of a new iteration.
+    pub fn start_iteration(&mut self) {
+        self.iterations.push((Instant::now(), self.spans.len()));
+    }
+
+    /// Leaves the top-level spans with the label in the current iteration out of the
+    /// measurements, including their children and their time in the iteration duration.
+    pub fn discard(&mut self, label: &'static str) {
+        let from = self.iterations.last().map_or(0, |(_, from)| *from);
+        for i in from..self.spans.len() {
+            self.spans[i].discarded = match self.spans[i].parent {
+                Some(parent) => self.spans[parent].discarded,
+                None => self.spans[i].label == label,
+            };
+        }
+    }
+
+    /// Spans of each iteration, without iterations everything is part of a single one.
+    fn iterations(&self) -> impl Iterator<Item = (Instant, &[Span])> {
+        let starts = if self.iterations.is_empty() {
+            vec![(self.start, 0)]
+        } else {
+            self.iterations.clone()
+        };
+        let ends = starts
+            .iter()
+            .skip(1)
+            .map(|(_, idx)| *idx)
+            .chain([self.spans.len()])
+            .collect::<Vec<_>>();
+        starts
+            .into_iter()
+            .zip(ends)
+            .map(|((start, from), to)| (start, &self.spans[from..to]))
+    }
+
     pub fn duration(&self) -> Duration {
-        self.measurements
-            .last()
-            .map(|(_, d)| d)
-            .unwrap_or(&self.start)
-            .duration_since(self.start)
+        self.iteration_durations().into_iter().sum()
+    }
+
+    /// Start of each iteration and the end of its last span.
+    pub fn iteration_bounds(&self) -> Vec<(Instant, Instant)> {
+        self.iterations()
+            .map(|(start, spans)| {
+                let end = spans.iter().map(|span| span.end).max().unwrap_or(start);
+                (start, end)
+            })
+            .collect()
+    }
+
+    pub fn iteration_durations(&self) -> Vec<Duration> {
+        self.iterations()
+            .zip(self.iteration_bounds())
+            .map(|((_, spans), (start, end))| {
+                let discarded = spans
+                    .iter()
+                    .filter(|span| span.discarded && span.parent.is_none())
+                    .map(|span| span.end.duration_since(span.start))
+                    .sum::<Duration>();
+                end.duration_since(start).saturating_sub(discarded)
+            })
+            .collect()
+    }
+
+    /// Label, start and end of every span, in order of their start.
+    pub fn spans(&self) -> impl Iterator<Item = (&'static str, Instant, Instant)> + '_ {
+        self.spans
+            .iter()
+            .filter(|span| !span.discarded)
+            .map(|span| (span.label, span.start, span.end))
     }
 
     pub fn measurements(&self) -> impl IntoIterator<Item = (&'static str, Duration)> {
-        let mut map = IndexMap::new();
-        let mut previous = self.start;
-        for (label, instant) in &self.measurements {
-            let duration = instant.duration_since(previous);
-            *map.entry(*label).or_insert(Duration::ZERO) += duration;
-            previous = *instant;
+        self.samples()
+            .into_iter()
+            .map(|(label, samples)| (label, samples.into_iter().sum()))
+    }
+
+    /// Duration of each top-level label per iteration.
+    pub fn samples(&self) -> IndexMap<&'static str, Vec<Duration>> {
+        self.tree()
+            .into_iter()
+            .filter(|node| node.depth == 0)
+            .map(|node| (node.label, node.total))
+            .collect()
+    }
+
+    /// Spans with the same path of labels combined into a single node, in depth-first order.
+    pub fn tree(&self) -> Vec<SpanNode> {
+        let count = self.iterations().count();
+        let mut nodes: Vec<(Option<usize>, SpanNode)> = vec![];
+        let mut paths = IndexMap::new();
+        // Node of each span, spans always come after their parent.
+        let mut span_nodes = Vec::with_capacity(self.spans.len());
+        for (i, (_, spans)) in self.iterations().enumerate() {
+            for span in spans {
+                if span.discarded {
+                    // Its children are discarded as well, so they never look up this node.
+                    span_nodes.push(usize::MAX);
+                    continue;
+                }
+                let parent = span.parent.map(|parent| span_nodes[parent]);
+                let node = *paths.entry((parent, span.label)).or_insert_with(|| {
+                    let depth = parent.map_or(0, |parent: usize| nodes[parent].1.depth + 1);
+                    nodes.push((
+                        parent,
+                        SpanNode {
+                            label: span.label,
+                            depth,
+                            total: vec![Duration::ZERO; count],
+                            self_time: vec![Duration::ZERO; count],
+                            allocations: vec![AllocStats::default(); count],
+                            counters: vec![PerfStats::default(); count],
+                        },
+                    ));
+                    nodes.len() - 1
+                });
+                span_nodes.push(node);
+
+                let duration = span.end.duration_since(span.start);
+                nodes[node].1.total[i] += duration;
+                nodes[node].1.self_time[i] += duration;
+                let allocations = &mut nodes[node].1.allocations[i];
+                allocations.bytes += span.allocations.bytes;
+                allocations.count += span.allocations.count;
+                allocations.peak = allocations.peak.max(span.allocations.peak);
+                let counters = &mut nodes[node].1.counters[i];
+                counters.instructions += span.counters.instructions;
+                counters.cycles += span.counters.cycles;
+                counters.branch_misses += span.counters.branch_misses;
+                counters.cache_misses += span.counters.cache_misses;
+                if let Some(parent) = parent {
+                    nodes[parent].1.self_time[i] =
+                        nodes[parent].1.self_time[i].saturating_sub(duration);
+                }
+            }
+        }
+
+        fn visit(
+            nodes: &[(Option<usize>, SpanNode)],
+            parent: Option<usize>,
+            result: &mut Vec<SpanNode>,
+        ) {
+            for (i, (_, node)) in nodes.iter().enumerate().filter(|(_, (p, _))| *p == parent) {
+                result.push(node.clone());
+                visit(nodes, Some(i), result);
+            }
         }
-        map
+        let mut result = Vec::with_capacity(nodes.len());
+        visit(&nodes, None, &mut result);
+        result
     }
 }
 impl Default for MeasureContext {
@@ -54,3 +256,136 @@ impl Default for MeasureContext {
         Self::new()
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    pub samples: usize,
+    pub outliers: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Sum of all samples, including the outliers.
+    pub total: Duration,
+}
+
+impl Statistics {
+    /// Outliers are rejected using Tukey's fences, outside 1.5 times the interquartile range.
+    pub fn from_samples(samples: &[Duration], reject_outliers: bool) -> Self {
+        let total = samples.iter().sum();
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        if reject_outliers && sorted.len() >= 4 {
+            let q1 = percentile(&sorted, 0.25);
+            let q3 = percentile(&sorted, 0.75);
+            let fence = (q3 - q1).mul_f64(1.5);
+            let low = q1.saturating_sub(fence);
+            let high = q3 + fence;
+            sorted.retain(|d| (low..=high).contains(d));
+        }
+        if sorted.is_empty() {
+            return Self::default();
+        }
+
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        Statistics {
+            samples: n,
+            outliers: samples.len() - n,
+            mean,
+            min: sorted[0],
+            median,
+            p95: percentile(&sorted, 0.95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            total,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_spans() {
+        let mut ctx = MeasureContext::new();
+        for _ in 0..2 {
+            ctx.start_iteration();
+            ctx.span("outer", |ctx| {
+                ctx.measure("inner", || std::thread::sleep(Duration::from_millis(2)));
+                std::thread::sleep(Duration::from_millis(1));
+            });
+            ctx.measure("after", || ());
+        }
+
+        let tree = ctx.tree();
+        let labels = tree.iter().map(|n| (n.label, n.depth)).collect::<Vec<_>>();
+        assert_eq!(labels, [("outer", 0), ("inner", 1), ("after", 0)]);
+        let (outer, inner) = (&tree[0], &tree[1]);
+        for i in 0..2 {
+            assert!(inner.total[i] >= Duration::from_millis(2));
+            assert!(outer.total[i] >= inner.total[i] + outer.self_time[i]);
+            assert!(outer.self_time[i] >= Duration::from_millis(1));
+        }
+        assert_eq!(
+            ctx.samples().keys().copied().collect::<Vec<_>>(),
+            ["outer", "after"]
+        );
+    }
+
+    #[test]
+    fn discard() {
+        let mut ctx = MeasureContext::new();
+        ctx.start_iteration();
+        ctx.span("part1", |ctx| {
+            ctx.measure("inner", || std::thread::sleep(Duration::from_millis(5)))
+        });
+        ctx.measure("part2", || ());
+        ctx.discard("part1");
+
+        let labels = ctx.tree().iter().map(|n| n.label).collect::<Vec<_>>();
+        assert_eq!(labels, ["part2"]);
+        assert_eq!(
+            ctx.spans().map(|(label, ..)| label).collect::<Vec<_>>(),
+            ["part2"]
+        );
+        assert!(ctx.duration() < Duration::from_millis(5));
+    }
+
+    #[test]
+    fn statistics() {
+        let samples = [5, 1, 3, 2, 4, 100].map(Duration::from_micros);
+
+        let stats = Statistics::from_samples(&samples, false);
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(3500));
+        assert_eq!(stats.p95, Duration::from_micros(100));
+
+        let stats = Statistics::from_samples(&samples, true);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.total, Duration::from_micros(115));
+    }
+}
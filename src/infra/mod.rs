@@ -1,4 +1,19 @@
+pub mod allocations;
+pub mod answers;
+pub mod baseline;
+pub mod error;
 pub mod input;
 pub mod measure;
+pub mod params;
+pub mod perf;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod selector;
 pub mod solution;
 pub mod solver;
+pub mod submit;
+#[cfg(test)]
+pub mod testing;
+pub mod trace;
+pub mod watch;
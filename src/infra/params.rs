@@ -0,0 +1,107 @@
+use crate::infra::error::SolveError;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Named puzzle constants, for values that differ between the examples and the real input.
+/// Remembers which keys were read, so that keys no solver uses can be reported.
+#[derive(Debug, Default)]
+pub struct Params(Vec<Param>);
+
+#[derive(Debug)]
+struct Param {
+    key: String,
+    value: String,
+    read: AtomicBool,
+}
+
+impl Params {
+    /// Sets a value, replacing an earlier value for the same key.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        self.0.retain(|param| param.key != key);
+        self.0.push(Param {
+            key,
+            value: value.into(),
+            read: AtomicBool::new(false),
+        });
+    }
+
+    /// Value for the key, or the default when it is not set.
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, SolveError>
+    where
+        T::Err: Display,
+    {
+        let Some(param) = self.0.iter().find(|param| param.key == key) else {
+            return Ok(default);
+        };
+        param.read.store(true, Ordering::Relaxed);
+        param.value.parse().map_err(|e| {
+            SolveError::Invalid(format!(
+                "invalid value `{}` for parameter {}: {}",
+                param.value, key, e
+            ))
+        })
+    }
+
+    /// Keys that were set but never read.
+    pub fn unused(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(|param| !param.read.load(Ordering::Relaxed))
+            .map(|param| param.key.as_str())
+    }
+}
+
+impl Clone for Params {
+    fn clone(&self) -> Self {
+        let mut params = Params::default();
+        params.extend(self.0.iter().map(|param| (&*param.key, &*param.value)));
+        params
+    }
+}
+
+/// Parses `KEY=VALUE`.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got `{}`", s)),
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Params {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter()
+            .for_each(|(key, value)| self.set(key, value));
+    }
+}
+
+impl From<&[(&str, &str)]> for Params {
+    fn from(pairs: &[(&str, &str)]) -> Self {
+        let mut params = Params::default();
+        params.extend(pairs.iter().copied());
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let mut params = Params::from(&[("rows", "3"), ("name", "x")][..]);
+        params.extend([parse_param("rows=4").unwrap()]);
+        assert_eq!(params.unused().collect::<Vec<_>>(), ["name", "rows"]);
+        assert_eq!(params.get("rows", 0u8), Ok(4));
+        assert_eq!(params.get("missing", 10usize), Ok(10));
+        assert_eq!(params.unused().collect::<Vec<_>>(), ["name"]);
+        assert!(matches!(
+            params.get("name", 0u8),
+            Err(SolveError::Invalid(_))
+        ));
+        assert_eq!(params.get("name", String::new()), Ok("x".to_string()));
+        assert!(params.unused().next().is_none());
+        assert!(parse_param("rows").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+/// Whether hardware counters are compiled in, using the `perf-counters` feature on Linux. They
+/// may still be unavailable at runtime, see [`PerfCounters::open`].
+pub const ENABLED: bool = cfg!(all(feature = "perf-counters", target_os = "linux"));
+
+/// Hardware events counted by the current thread during a span, in user space only.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PerfStats {
+    pub instructions: u64,
+    pub cycles: u64,
+    pub branch_misses: u64,
+    pub cache_misses: u64,
+}
+
+impl PerfStats {
+    /// Mean of each counter over the samples.
+    pub fn average(samples: &[PerfStats]) -> Self {
+        let n = samples.len().max(1) as u64;
+        let mean = |f: fn(&PerfStats) -> u64| samples.iter().map(f).sum::<u64>() / n;
+        PerfStats {
+            instructions: mean(|s| s.instructions),
+            cycles: mean(|s| s.cycles),
+            branch_misses: mean(|s| s.branch_misses),
+            cache_misses: mean(|s| s.cache_misses),
+        }
+    }
+
+    /// Instructions per cycle.
+    pub fn ipc(&self) -> f64 {
+        self.instructions as f64 / self.cycles.max(1) as f64
+    }
+
+    /// Difference with counter values read earlier.
+    pub fn since(&self, start: &PerfStats) -> PerfStats {
+        PerfStats {
+            instructions: self.instructions.wrapping_sub(start.instructions),
+            cycles: self.cycles.wrapping_sub(start.cycles),
+            branch_misses: self.branch_misses.wrapping_sub(start.branch_misses),
+            cache_misses: self.cache_misses.wrapping_sub(start.cache_misses),
+        }
+    }
+}
+
+/// Running hardware counters of the thread that opened them.
+pub struct PerfCounters {
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    group: linux::Group,
+}
+
+impl PerfCounters {
+    /// Fails when the feature is disabled, the kernel does not allow it (see
+    /// `/proc/sys/kernel/perf_event_paranoid`) or the CPU does not expose the counters, which is
+    /// common in virtual machines.
+    pub fn open() -> std::io::Result<Self> {
+        #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+        return Ok(PerfCounters {
+            group: linux::Group::open()?,
+        });
+        #[cfg(not(all(feature = "perf-counters", target_os = "linux")))]
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "hardware counters require the perf-counters feature on Linux",
+        ))
+    }
+
+    /// Current counter values, zero when they cannot be read.
+    pub fn read(&self) -> PerfStats {
+        #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+        return self.group.read().unwrap_or_default();
+        #[cfg(not(all(feature = "perf-counters", target_os = "linux")))]
+        PerfStats::default()
+    }
+}
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+mod linux {
+    use super::PerfStats;
+    use perf_event_open_sys::bindings::{
+        PERF_FLAG_FD_CLOEXEC, perf_event_attr, perf_event_read_format_PERF_FORMAT_GROUP,
+        perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES, perf_hw_id_PERF_COUNT_HW_CACHE_MISSES,
+        perf_hw_id_PERF_COUNT_HW_CPU_CYCLES, perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+        perf_type_id_PERF_TYPE_HARDWARE,
+    };
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    /// Events in the order they are read, the first one leads the group.
+    const EVENTS: [u32; 4] = [
+        perf_hw_id_PERF_COUNT_HW_CPU_CYCLES,
+        perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+        perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES,
+        perf_hw_id_PERF_COUNT_HW_CACHE_MISSES,
+    ];
+
+    pub struct Group {
+        leader: File,
+        _members: Vec<File>,
+    }
+
+    fn open_event(event: u32, leader: Option<&File>) -> io::Result<File> {
+        let mut attr = perf_event_attr {
+            type_: perf_type_id_PERF_TYPE_HARDWARE,
+            size: size_of::<perf_event_attr>() as u32,
+            config: event as u64,
+            read_format: perf_event_read_format_PERF_FORMAT_GROUP as u64,
+            ..Default::default()
+        };
+        attr.set_exclude_kernel(1);
+        attr.set_exclude_hv(1);
+        let group_fd = leader.map_or(-1, |leader| leader.as_raw_fd());
+        // SAFETY: the attributes are initialized and outlive the call.
+        let fd = unsafe {
+            perf_event_open_sys::perf_event_open(
+                &mut attr,
+                0,
+                -1,
+                group_fd,
+                PERF_FLAG_FD_CLOEXEC as _,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the file descriptor was just opened and is owned by nobody else.
+        Ok(unsafe { File::from_raw_fd(fd) })
+    }
+
+    impl Group {
+        pub fn open() -> io::Result<Self> {
+            let leader = open_event(EVENTS[0], None)?;
+            let members = EVENTS[1..]
+                .iter()
+                .map(|event| open_event(*event, Some(&leader)))
+                .collect::<io::Result<_>>()?;
+            Ok(Group {
+                leader,
+                _members: members,
+            })
+        }
+
+        pub fn read(&self) -> io::Result<PerfStats> {
+            // Number of events followed by their values.
+            let mut buffer = [0u8; 8 * (EVENTS.len() + 1)];
+            (&self.leader).read_exact(&mut buffer)?;
+            let value =
+                |i: usize| u64::from_ne_bytes(buffer[8 * i..8 * (i + 1)].try_into().unwrap());
+            Ok(PerfStats {
+                cycles: value(1),
+                instructions: value(2),
+                branch_misses: value(3),
+                cache_misses: value(4),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters() {
+        // Counters are often unavailable in containers and virtual machines.
+        let Ok(counters) = PerfCounters::open() else {
+            return;
+        };
+        let start = counters.read();
+        std::hint::black_box((0..10_000u64).sum::<u64>());
+        let stats = counters.read().since(&start);
+        assert!(stats.instructions > 0);
+        assert!(stats.cycles > 0);
+    }
+}
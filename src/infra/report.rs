@@ -0,0 +1,672 @@
+use crate::infra::allocations::AllocStats;
+use crate::infra::answers::Verification;
+use crate::infra::baseline::Comparison;
+use crate::infra::measure::Statistics;
+use crate::infra::perf::PerfStats;
+use crate::infra::solution::Solution;
+use crate::util::ocr::ocr;
+use serde_json::json;
+use std::io::{Result, Write};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    /// Aligned table with a row per run, printed once all runs finished.
+    Table,
+}
+
+/// Outcome of running a single solver, with statistics over the per-iteration durations.
+#[derive(Debug)]
+pub struct Report {
+    pub name: String,
+    pub year: u16,
+    pub day: u8,
+    pub parts: Vec<PartReport>,
+    pub duration: Statistics,
+    /// CPU time of the thread running the solver, including the warmup iterations.
+    pub cpu_time: Duration,
+    pub measurements: Vec<(String, Statistics)>,
+    /// Nested spans in depth-first order.
+    pub spans: Vec<SpanReport>,
+    pub baseline: Vec<Comparison>,
+}
+
+#[derive(Debug)]
+pub struct SpanReport {
+    pub label: String,
+    pub depth: usize,
+    pub total: Statistics,
+    pub self_time: Statistics,
+    /// Only available with the `count-allocations` feature.
+    pub allocations: Option<AllocStats>,
+    /// Only available with the `perf-counters` feature, when the counters can be opened.
+    pub counters: Option<PerfStats>,
+}
+
+fn format_counters(counters: &PerfStats) -> String {
+    format!(
+        "{} instructions, {} cycles (IPC {:.2}), {} branch misses, {} cache misses",
+        counters.instructions,
+        counters.cycles,
+        counters.ipc(),
+        counters.branch_misses,
+        counters.cache_misses
+    )
+}
+
+fn format_allocations(allocations: &AllocStats) -> String {
+    format!(
+        "{} bytes in {} allocations, peak {} bytes",
+        allocations.bytes, allocations.count, allocations.peak
+    )
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: u8,
+    pub solution: Solution,
+    pub verification: Option<Verification>,
+}
+
+/// Totals over all runs: the summed mean iteration time, the CPU time summed over every thread
+/// and the elapsed wall-clock time.
+#[derive(Clone, Copy, Debug)]
+pub struct Totals {
+    pub duration: Duration,
+    pub cpu_time: Duration,
+    pub wall: Duration,
+}
+
+impl Report {
+    fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    /// Reads back a record of the JSON report, e.g. from another process. Answers become
+    /// strings, spans only keep their mean times and baseline comparisons are left out.
+    pub fn from_json(record: &serde_json::Value) -> Option<Report> {
+        let parts = (1..=2)
+            .filter_map(|part| {
+                let solution = match record.get(format!("part{}", part))? {
+                    serde_json::Value::String(answer) => Solution::Str(answer.clone()),
+                    _ => Solution::Nothing(),
+                };
+                let verification =
+                    record
+                        .get(format!("part{}_verification", part))
+                        .map(|verification| match verification["status"].as_str() {
+                            Some("pass") => Verification::Pass,
+                            Some("fail") => Verification::Fail(
+                                verification["expected"]
+                                    .as_str()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            ),
+                            _ => Verification::Missing,
+                        });
+                Some(PartReport {
+                    part,
+                    solution,
+                    verification,
+                })
+            })
+            .collect();
+        let measurements = record["measurements"]
+            .as_array()?
+            .iter()
+            .map(|measurement| {
+                let label = measurement["label"].as_str()?.to_string();
+                Some((label, statistics_from_json(measurement)?))
+            })
+            .collect::<Option<_>>()?;
+        let spans = record["spans"]
+            .as_array()?
+            .iter()
+            .map(|span| {
+                let mean = |key| {
+                    Some(Statistics {
+                        mean: nanos(span, key)?,
+                        ..Statistics::default()
+                    })
+                };
+                Some(SpanReport {
+                    label: span["label"].as_str()?.to_string(),
+                    depth: span["depth"].as_u64()? as usize,
+                    total: mean("total_ns")?,
+                    self_time: mean("self_ns")?,
+                    allocations: (|| {
+                        Some(AllocStats {
+                            bytes: span.get("alloc_bytes")?.as_u64()?,
+                            count: span.get("alloc_count")?.as_u64()?,
+                            peak: span.get("alloc_peak_bytes")?.as_u64()?,
+                        })
+                    })(),
+                    counters: (|| {
+                        Some(PerfStats {
+                            instructions: span.get("instructions")?.as_u64()?,
+                            cycles: span.get("cycles")?.as_u64()?,
+                            branch_misses: span.get("branch_misses")?.as_u64()?,
+                            cache_misses: span.get("cache_misses")?.as_u64()?,
+                        })
+                    })(),
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(Report {
+            name: record["name"].as_str()?.to_string(),
+            year: record["year"].as_u64()?.try_into().ok()?,
+            day: record["day"].as_u64()?.try_into().ok()?,
+            parts,
+            duration: statistics_from_json(record)?,
+            cpu_time: nanos(record, "cpu_ns")?,
+            measurements,
+            spans,
+            baseline: vec![],
+        })
+    }
+}
+
+fn nanos(value: &serde_json::Value, key: &str) -> Option<Duration> {
+    value.get(key)?.as_u64().map(Duration::from_nanos)
+}
+
+fn statistics_from_json(value: &serde_json::Value) -> Option<Statistics> {
+    Some(Statistics {
+        samples: value.get("samples")?.as_u64()? as usize,
+        outliers: value.get("outliers")?.as_u64()? as usize,
+        mean: nanos(value, "duration_ns")?,
+        min: nanos(value, "min_ns")?,
+        median: nanos(value, "median_ns")?,
+        p95: nanos(value, "p95_ns")?,
+        stddev: nanos(value, "stddev_ns")?,
+        total: nanos(value, "total_ns")?,
+    })
+}
+
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+    count: usize,
+    rows: Vec<[String; 5]>,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Reporter {
+            format,
+            out,
+            count: 0,
+            rows: vec![],
+        }
+    }
+
+    pub fn report(&mut self, report: &Report) -> Result<()> {
+        match self.format {
+            Format::Text => self.text(report)?,
+            Format::Json => self.json(report)?,
+            Format::Csv => self.csv(report)?,
+            Format::Table => self.table_row(report),
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Finishes the output, the totals are only printed for text and tables.
+    pub fn finish(mut self, totals: Option<Totals>) -> Result<()> {
+        if self.format == Format::Table {
+            self.table()?;
+        }
+        match self.format {
+            Format::Text | Format::Table => {
+                if let Some(totals) = totals {
+                    writeln!(self.out, "Total time: {:?}", totals.duration)?;
+                    writeln!(
+                        self.out,
+                        "Summed CPU time: {:?}, wall-clock time: {:?}",
+                        totals.cpu_time, totals.wall
+                    )?;
+                }
+            }
+            Format::Json => {
+                if self.count == 0 {
+                    write!(self.out, "[")?;
+                }
+                writeln!(self.out, "\n]")?;
+            }
+            Format::Csv => {
+                if self.count == 0 {
+                    self.csv_header()?;
+                }
+            }
+        }
+        self.out.flush()
+    }
+
+    fn text(&mut self, report: &Report) -> Result<()> {
+        let name = &report.name;
+        for PartReport {
+            part,
+            solution,
+            verification,
+        } in &report.parts
+        {
+            let solution = match solution {
+                // Drawings start on their own line.
+                Solution::Grid(_) => format!("\n{}", solution.to_string().trim_end_matches('\n')),
+                solution => solution.to_string(),
+            };
+            match verification {
+                Some(verification) => {
+                    writeln!(self.out, "{name}/part{part}: {solution} ({verification})")?
+                }
+                None => writeln!(self.out, "{name}/part{part}: {solution}")?,
+            }
+        }
+
+        write!(self.out, "{}/time: {:?}", name, report.duration.mean)?;
+        if !report.measurements.is_empty() {
+            let measurements = report
+                .measurements
+                .iter()
+                .map(|(label, stats)| format!("{}: {:?}", label, stats.mean))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(self.out, " ({})", measurements)?;
+        }
+        writeln!(self.out)?;
+
+        if report.spans.iter().any(|span| span.depth > 0) {
+            writeln!(self.out, "{}/spans:", name)?;
+            for span in &report.spans {
+                write!(
+                    self.out,
+                    "{:indent$}{}: {:?} (self {:?}",
+                    "",
+                    span.label,
+                    span.total.mean,
+                    span.self_time.mean,
+                    indent = 2 * (span.depth + 1)
+                )?;
+                if let Some(allocations) = &span.allocations {
+                    write!(self.out, ", {}", format_allocations(allocations))?;
+                }
+                writeln!(self.out, ")")?;
+            }
+        }
+
+        let allocations = report
+            .spans
+            .iter()
+            .filter(|span| span.depth == 0)
+            .filter_map(|span| Some((&span.label, span.allocations.as_ref()?)))
+            .map(|(label, allocations)| format!("{}: {}", label, format_allocations(allocations)))
+            .collect::<Vec<_>>();
+        if !allocations.is_empty() {
+            writeln!(self.out, "{}/allocations: {}", name, allocations.join("; "))?;
+        }
+
+        let counters = report
+            .spans
+            .iter()
+            .filter(|span| span.depth == 0)
+            .filter_map(|span| Some((&span.label, span.counters.as_ref()?)))
+            .map(|(label, counters)| format!("{}: {}", label, format_counters(counters)))
+            .collect::<Vec<_>>();
+        if !counters.is_empty() {
+            writeln!(self.out, "{}/counters: {}", name, counters.join("; "))?;
+        }
+
+        if report.duration.samples + report.duration.outliers > 1 {
+            let stats = [("time", &report.duration)].into_iter().chain(
+                report
+                    .measurements
+                    .iter()
+                    .map(|(label, s)| (label.as_str(), s)),
+            );
+            for (label, stats) in stats {
+                write!(
+                    self.out,
+                    "{}/stats/{}: min {:?}, median {:?}, p95 {:?}, stddev {:?}",
+                    name, label, stats.min, stats.median, stats.p95, stats.stddev
+                )?;
+                if stats.outliers > 0 {
+                    write!(self.out, " ({} outliers)", stats.outliers)?;
+                }
+                writeln!(self.out)?;
+            }
+        }
+
+        for comparison in &report.baseline {
+            writeln!(
+                self.out,
+                "{}/baseline/{}: {:?} -> {:?} ({:+.1}%){}",
+                name,
+                comparison.label,
+                comparison.baseline,
+                comparison.current,
+                comparison.change(),
+                if comparison.regression {
+                    ", REGRESSION"
+                } else {
+                    ""
+                }
+            )?;
+        }
+        Ok(())
+    }
+
+    fn json(&mut self, report: &Report) -> Result<()> {
+        let solution = |solution: &Solution| match solution {
+            Solution::Nothing() => serde_json::Value::Null,
+            // The letters when they can be read, so the answer can be verified and submitted.
+            Solution::Grid(grid) => ocr(grid).unwrap_or_else(|| solution.to_string()).into(),
+            solution => solution.to_string().into(),
+        };
+        let statistics = |stats: &Statistics| {
+            json!({
+                "duration_ns": stats.mean.as_nanos() as u64,
+                "min_ns": stats.min.as_nanos() as u64,
+                "median_ns": stats.median.as_nanos() as u64,
+                "p95_ns": stats.p95.as_nanos() as u64,
+                "stddev_ns": stats.stddev.as_nanos() as u64,
+                "samples": stats.samples,
+                "outliers": stats.outliers,
+                "total_ns": stats.total.as_nanos() as u64,
+            })
+        };
+        let mut value = json!({
+            "name": report.name,
+            "year": report.year,
+            "day": report.day,
+            "cpu_ns": report.cpu_time.as_nanos() as u64,
+            "measurements": report.measurements.iter().map(|(label, stats)| {
+                let mut value = statistics(stats);
+                value["label"] = label.as_str().into();
+                value
+            }).collect::<Vec<_>>(),
+            "spans": report.spans.iter().map(|span| {
+                let mut value = json!({
+                    "label": span.label,
+                    "depth": span.depth,
+                    "total_ns": span.total.mean.as_nanos() as u64,
+                    "self_ns": span.self_time.mean.as_nanos() as u64,
+                });
+                if let Some(allocations) = &span.allocations {
+                    value["alloc_bytes"] = allocations.bytes.into();
+                    value["alloc_count"] = allocations.count.into();
+                    value["alloc_peak_bytes"] = allocations.peak.into();
+                }
+                if let Some(counters) = &span.counters {
+                    value["instructions"] = counters.instructions.into();
+                    value["cycles"] = counters.cycles.into();
+                    value["branch_misses"] = counters.branch_misses.into();
+                    value["cache_misses"] = counters.cache_misses.into();
+                }
+                value
+            }).collect::<Vec<_>>(),
+        });
+        for (key, stat) in statistics(&report.duration).as_object().unwrap() {
+            value[key] = stat.clone();
+        }
+        if !report.baseline.is_empty() {
+            value["baseline"] = report
+                .baseline
+                .iter()
+                .map(|comparison| {
+                    json!({
+                        "label": comparison.label,
+                        "baseline_ns": comparison.baseline.as_nanos() as u64,
+                        "current_ns": comparison.current.as_nanos() as u64,
+                        "change_percent": comparison.change(),
+                        "regression": comparison.regression,
+                    })
+                })
+                .collect();
+        }
+        for part in &report.parts {
+            value[format!("part{}", part.part)] = solution(&part.solution);
+            if let Some(verification) = &part.verification {
+                let mut entry = json!({ "status": verification.status() });
+                if let Verification::Fail(expected) = verification {
+                    entry["expected"] = expected.as_str().into();
+                }
+                value[format!("part{}_verification", part.part)] = entry;
+            }
+        }
+
+        write!(self.out, "{}", if self.count == 0 { "[\n" } else { ",\n" })?;
+        write!(self.out, "  {}", value)
+    }
+
+    fn table_row(&mut self, report: &Report) {
+        let part = |part| {
+            let Some(PartReport {
+                solution,
+                verification,
+                ..
+            }) = report.part(part)
+            else {
+                return "-".to_string();
+            };
+            let solution = match solution {
+                Solution::Grid(grid) => ocr(grid).unwrap_or_else(|| "<grid>".to_string()),
+                solution => solution.to_string(),
+            };
+            match verification {
+                Some(verification @ (Verification::Pass | Verification::Fail(_))) => {
+                    format!("{} ({})", solution, verification)
+                }
+                _ => solution,
+            }
+        };
+        self.rows.push([
+            report.name.clone(),
+            part(1),
+            part(2),
+            format!("{:?}", report.duration.mean),
+            format!("{:?}", report.duration.median),
+        ]);
+    }
+
+    fn table(&mut self) -> Result<()> {
+        let header = ["name", "part1", "part2", "time", "median"].map(str::to_string);
+        let header = [header];
+        let rows = header.iter().chain(&self.rows);
+        let mut widths = [0; 5];
+        for row in rows.clone() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in rows {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(self.out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+
+    fn csv_header(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            "name,year,day,part1,part2,duration_ns,min_ns,median_ns,p95_ns,stddev_ns,measurements,part1_verification,part2_verification"
+        )
+    }
+
+    fn csv(&mut self, report: &Report) -> Result<()> {
+        if self.count == 0 {
+            self.csv_header()?;
+        }
+        let measurements = report
+            .measurements
+            .iter()
+            .map(|(label, stats)| format!("{}={}", label, stats.mean.as_nanos()))
+            .collect::<Vec<_>>()
+            .join(";");
+        let solution = |part| {
+            report
+                .part(part)
+                .map(|p| match &p.solution {
+                    // The letters when they can be read, like the JSON output.
+                    Solution::Grid(grid) => ocr(grid).unwrap_or_else(|| p.solution.to_string()),
+                    solution => solution.to_string(),
+                })
+                .unwrap_or_default()
+        };
+        let verification = |part| {
+            report
+                .part(part)
+                .and_then(|p| p.verification.as_ref())
+                .map(|v| v.status())
+                .unwrap_or_default()
+        };
+        let fields = [
+            report.name.clone(),
+            report.year.to_string(),
+            report.day.to_string(),
+            solution(1),
+            solution(2),
+            report.duration.mean.as_nanos().to_string(),
+            report.duration.min.as_nanos().to_string(),
+            report.duration.median.as_nanos().to_string(),
+            report.duration.p95.as_nanos().to_string(),
+            report.duration.stddev.as_nanos().to_string(),
+            measurements,
+            verification(1).to_string(),
+            verification(2).to_string(),
+        ];
+        let fields = fields.iter().map(|f| csv_escape(f)).collect::<Vec<_>>();
+        writeln!(self.out, "{}", fields.join(","))
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing;
+    use crate::util::grid::Grid;
+
+    fn report() -> Report {
+        Report {
+            parts: vec![
+                PartReport {
+                    part: 1,
+                    solution: 3u32.into(),
+                    verification: Some(Verification::Pass),
+                },
+                PartReport {
+                    part: 2,
+                    solution: "a,\"b\"".into(),
+                    verification: Some(Verification::Fail("c".to_string())),
+                },
+            ],
+            ..testing::report(1500, &[("parse", 1000), ("part1", 500)])
+        }
+    }
+
+    fn output(format: Format, reports: &[Report]) -> String {
+        let mut out = vec![];
+        let mut reporter = Reporter::new(format, &mut out);
+        for report in reports {
+            reporter.report(report).unwrap();
+        }
+        reporter.finish(None).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json() {
+        let json = output(Format::Json, &[report(), report()]);
+        let records: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records.len(), 2);
+        let record = &records[0];
+        assert_eq!(record["name"], "2025/day05");
+        assert_eq!(record["day"], 5);
+        assert_eq!(record["part1"], "3");
+        assert_eq!(record["median_ns"], 1500);
+        assert_eq!(record["measurements"][0]["label"], "parse");
+        assert_eq!(record["measurements"][0]["median_ns"], 1000);
+        assert_eq!(record["part1_verification"]["status"], "pass");
+        assert_eq!(
+            record["part2_verification"],
+            serde_json::json!({ "status": "fail", "expected": "c" })
+        );
+    }
+
+    #[test]
+    fn from_json() {
+        let json = output(Format::Json, &[report()]);
+        let records: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let report = Report::from_json(&records[0]).unwrap();
+        assert_eq!(report.measurements[0].0, "parse");
+        assert_eq!(report.duration.total, Duration::from_nanos(1500));
+        assert_eq!(report.cpu_time, Duration::from_nanos(1500));
+        assert_eq!(
+            output(Format::Csv, &[report]),
+            output(Format::Csv, &[self::report()])
+        );
+        assert!(Report::from_json(&serde_json::json!({ "name": "x" })).is_none());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(output(Format::Json, &[]), "[\n]\n");
+        assert!(
+            serde_json::from_str::<Vec<serde_json::Value>>("[\n]\n")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(output(Format::Csv, &[]).lines().count(), 1);
+    }
+
+    #[test]
+    fn csv() {
+        let csv = output(Format::Csv, &[report()]);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "name,year,day,part1,part2,duration_ns,min_ns,median_ns,p95_ns,stddev_ns,measurements,part1_verification,part2_verification",
+                "2025/day05,2025,5,3,\"a,\"\"b\"\"\",1500,1500,1500,1500,0,parse=1000;part1=500,pass,fail",
+            ]
+        );
+
+        let drawing = [
+            "#..#.###", "#..#..#.", "####..#.", "#..#..#.", "#..#..#.", "#..#.###",
+        ];
+        let mut report = report();
+        report.parts[0].solution =
+            Grid::from_rows(drawing.map(|row| row.bytes().map(|b| b == b'#'))).into();
+        let csv = output(Format::Csv, &[report]);
+        assert!(
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("2025/day05,2025,5,HI,")
+        );
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    }
+}
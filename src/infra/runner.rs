@@ -0,0 +1,142 @@
+use crate::infra::error::SolveError;
+use crate::infra::measure::MeasureContext;
+use crate::infra::params::Params;
+use crate::infra::solution::SolutionTuple;
+use crate::infra::solver::{SolveResult, Solver};
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Solves both parts of any input once, with the default parameters and without keeping the
+/// measurements.
+pub fn solve(solver: &Solver, input: &str) -> SolveResult {
+    solver.solve(&mut MeasureContext::new(), input, &Params::default(), None)
+}
+
+/// CPU time used by the current thread so far, zero where it cannot be read.
+pub fn thread_cpu_time() -> Duration {
+    #[cfg(unix)]
+    {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: the timespec is valid for writes for the duration of the call.
+        if unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) } == 0 {
+            return Duration::new(time.tv_sec as u64, time.tv_nsec as u32);
+        }
+    }
+    Duration::ZERO
+}
+
+/// Runs the solver `warmup + repeat` times, measuring only the repeated iterations. Stops at the
+/// first error and panics when a repetition gives a different solution.
+pub fn execute(
+    solver: &Solver,
+    input: &str,
+    params: &Params,
+    part: Option<u8>,
+    warmup: u32,
+    repeat: u32,
+) -> Result<(SolutionTuple, MeasureContext), SolveError> {
+    assert!(repeat > 0);
+    {
+        let mut ctx = MeasureContext::new();
+        for _ in 0..warmup {
+            black_box(solver.solve(&mut ctx, black_box(input), params, part)?);
+        }
+    }
+
+    let mut ctx = MeasureContext::with_capacity(3 * repeat as usize);
+    ctx.start_iteration();
+    let solution = solver.solve(&mut ctx, black_box(input), params, part)?;
+    for _ in 0..repeat - 1 {
+        ctx.start_iteration();
+        assert_eq!(
+            &solver.solve(&mut ctx, black_box(input), params, part)?,
+            &solution
+        );
+    }
+    Ok((solution, ctx))
+}
+
+/// Calls `f` for every index on up to `jobs` threads. Results are passed to `consume` in index
+/// order, as soon as all earlier results are available.
+pub fn run_ordered<T: Send>(
+    count: usize,
+    jobs: usize,
+    f: impl Fn(usize) -> T + Sync,
+    mut consume: impl FnMut(usize, T),
+) {
+    if jobs <= 1 {
+        (0..count).for_each(|i| consume(i, f(i)));
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= count {
+                        break;
+                    }
+                    if sender.send((i, f(i))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&expected) {
+                consume(expected, result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_time() {
+        let start = thread_cpu_time();
+        let busy = std::time::Instant::now();
+        while busy.elapsed() < Duration::from_millis(5) {
+            black_box(());
+        }
+        // Sleeping does not use the CPU.
+        let sleep = thread_cpu_time();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(sleep - start >= Duration::from_millis(4));
+        assert!(thread_cpu_time() - sleep < Duration::from_millis(10));
+    }
+
+    #[test]
+    fn ordered() {
+        let mut results = vec![];
+        run_ordered(
+            20,
+            4,
+            |i| {
+                std::thread::sleep(std::time::Duration::from_micros(((20 - i) * 50) as u64));
+                i * 2
+            },
+            |i, result| results.push((i, result)),
+        );
+        assert_eq!(results, (0..20).map(|i| (i, i * 2)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::infra::solver::day_to_name;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub const DAY_DIRECTORY: &str = "./src/day";
+
+const TEMPLATE: &str = include_str!("../day/day00.rs");
+
+/// Source of a new day from the day00 template, optionally with the example input filled in.
+pub fn day_source(day: u8, example: Option<&str>) -> String {
+    let mut source = TEMPLATE
+        .replace(
+            "crate::register!(SOLVER, 0,",
+            &format!("crate::register!(SOLVER, {},", day),
+        )
+        .replace(
+            "crate::register_example!(EXAMPLE, 0,",
+            &format!("crate::register_example!(EXAMPLE, {},", day),
+        );
+    if let Some(example) = example {
+        let escaped = example
+            .trim_end_matches(['\r', '\n'])
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        source = source.replace(
+            "const EXAMPLE_INPUT: &str = \"\";",
+            &format!("const EXAMPLE_INPUT: &str = \"{}\";", escaped),
+        );
+    }
+    source
+}
+
+/// Creates `dayNN.rs` in the directory and declares it in its `mod.rs`, failing when the day
+/// already exists.
+pub fn create_day(directory: &Path, day: u8, example: Option<&str>) -> std::io::Result<PathBuf> {
+    let name = day_to_name(day);
+    let path = directory.join(format!("{}.rs", name));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?
+        .write_all(day_source(day, example).as_bytes())?;
+
+    let mod_path = directory.join("mod.rs");
+    let content = std::fs::read_to_string(&mod_path)?;
+    let mut lines = content.lines().collect::<Vec<_>>();
+    let declaration = format!("mod {};", name);
+    if !lines.contains(&declaration.as_str()) {
+        // After the last smaller declaration, or before the first one, leaving the other lines.
+        let is_mod = |line: &&str| line.starts_with("mod ");
+        let index = match lines
+            .iter()
+            .rposition(|line| is_mod(line) && *line < declaration.as_str())
+        {
+            Some(index) => index + 1,
+            None => lines.iter().position(is_mod).unwrap_or(lines.len()),
+        };
+        lines.insert(index, &declaration);
+        std::fs::write(&mod_path, lines.join("\n") + "\n")?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::TempDir;
+
+    #[test]
+    fn create() {
+        let directory = TempDir::new("scaffold");
+        let content = "//! Days.\n\nmod day01;\nmod day12;\n\npub use day12::SOLVER;\n";
+        std::fs::write(directory.join("mod.rs"), content).unwrap();
+
+        let path = create_day(directory.path(), 9, Some("a\"b\n1\n")).unwrap();
+        let source = std::fs::read_to_string(&path).unwrap();
+        assert!(source.contains("crate::register!(SOLVER, 9,"));
+        assert!(source.contains("crate::register_example!(EXAMPLE, 9, input: EXAMPLE_INPUT);"));
+        assert!(source.contains("const EXAMPLE_INPUT: &str = \"a\\\"b\n1\";"));
+        assert_eq!(
+            std::fs::read_to_string(directory.join("mod.rs")).unwrap(),
+            "//! Days.\n\nmod day01;\nmod day09;\nmod day12;\n\npub use day12::SOLVER;\n"
+        );
+
+        create_day(directory.path(), 13, None).unwrap();
+        create_day(directory.path(), 0, None).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(directory.join("mod.rs")).unwrap(),
+            "//! Days.\n\nmod day00;\nmod day01;\nmod day09;\nmod day12;\nmod day13;\n\npub use day12::SOLVER;\n"
+        );
+
+        let error = create_day(directory.path(), 9, None).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::AlreadyExists);
+    }
+}
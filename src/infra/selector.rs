@@ -0,0 +1,201 @@
+use crate::infra::solver::{Solver, day_to_name};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Selects solvers using `[YEAR/]DAYS[:PART]`. Days are `all`, `latest` or a comma-separated list
+/// of days and inclusive ranges, e.g. `3`, `3..7`, `1,4,8`, `2025/5:2`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selector {
+    pub year: Option<u16>,
+    pub days: Days,
+    pub part: Option<u8>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Days {
+    All,
+    Latest,
+    List(Vec<RangeInclusive<u8>>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SelectorError {
+    Invalid(String),
+    UnknownDay(Option<u16>, u8),
+    NoMatch(String),
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::Invalid(s) => write!(f, "invalid selector `{}`", s),
+            SelectorError::UnknownDay(Some(year), day) => {
+                write!(f, "unknown day {}/{}", year, day_to_name(*day))
+            }
+            SelectorError::UnknownDay(None, day) => write!(f, "unknown day {}", day_to_name(*day)),
+            SelectorError::NoMatch(s) => write!(f, "no solvers match `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl Default for Selector {
+    fn default() -> Self {
+        Selector {
+            year: None,
+            days: Days::All,
+            part: None,
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SelectorError::Invalid(s.to_string());
+
+        let (rest, part) = match s.rsplit_once(':') {
+            Some((rest, part)) => match part.parse() {
+                Ok(part @ (1 | 2)) => (rest, Some(part)),
+                _ => return Err(invalid()),
+            },
+            None => (s, None),
+        };
+        let (year, days) = match rest.split_once('/') {
+            Some((year, days)) => (Some(year.parse().map_err(|_| invalid())?), days),
+            None => (None, rest),
+        };
+        let days = match days {
+            "all" => Days::All,
+            "latest" => Days::Latest,
+            days => Days::List(
+                days.split(',')
+                    .map(|item| {
+                        let range = match item.split_once("..") {
+                            Some((start, end)) => {
+                                let end = end.strip_prefix('=').unwrap_or(end);
+                                start.parse()?..=end.parse()?
+                            }
+                            None => item.parse()?..=item.parse()?,
+                        };
+                        Ok(range)
+                    })
+                    .collect::<Result<_, std::num::ParseIntError>>()
+                    .map_err(|_| invalid())?,
+            ),
+        };
+
+        Ok(Selector { year, days, part })
+    }
+}
+
+impl Selector {
+    /// Matching solvers ordered by year and day.
+    pub fn select<'a>(
+        &self,
+        solvers: impl IntoIterator<Item = &'a Solver>,
+    ) -> Result<Vec<&'a Solver>, SelectorError> {
+        let mut candidates = solvers
+            .into_iter()
+            .filter(|s| self.year.is_none_or(|year| s.year == year))
+            .collect::<Vec<_>>();
+        candidates.sort_unstable_by_key(|s| (s.year, s.day));
+
+        let selected = match &self.days {
+            Days::All => candidates,
+            Days::Latest => candidates.pop().into_iter().collect(),
+            Days::List(ranges) => {
+                for range in ranges {
+                    if candidates.iter().any(|s| range.contains(&s.day)) {
+                        continue;
+                    }
+                    return Err(if range.start() == range.end() {
+                        SelectorError::UnknownDay(self.year, *range.start())
+                    } else {
+                        SelectorError::NoMatch(format!("{}..{}", range.start(), range.end()))
+                    });
+                }
+                candidates.retain(|s| ranges.iter().any(|range| range.contains(&s.day)));
+                candidates
+            }
+        };
+        if selected.is_empty() {
+            return Err(SelectorError::NoMatch(match self.year {
+                Some(year) => year.to_string(),
+                None => "all".to_string(),
+            }));
+        }
+        Ok(selected)
+    }
+
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::solution::SolutionTuple;
+    use crate::infra::solver::Solve;
+
+    fn solver(year: u16, day: u8) -> Solver {
+        Solver::new(
+            year,
+            day,
+            Solve::Both(|_, _, _| Ok(SolutionTuple((0u8).into(), (0u8).into()))),
+        )
+    }
+
+    fn select(selector: &str, solvers: &[Solver]) -> Result<Vec<(u16, u8)>, SelectorError> {
+        let selector = selector.parse::<Selector>()?;
+        Ok(selector
+            .select(solvers)?
+            .into_iter()
+            .map(|s| (s.year, s.day))
+            .collect())
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "2024/3..7:2".parse(),
+            Ok(Selector {
+                year: Some(2024),
+                days: Days::List(vec![3..=7]),
+                part: Some(2),
+            })
+        );
+        assert_eq!(
+            "1,4..=5".parse::<Selector>().map(|s| s.days),
+            Ok(Days::List(vec![1..=1, 4..=5]))
+        );
+        assert!("5:3".parse::<Selector>().is_err());
+        assert!("x".parse::<Selector>().is_err());
+    }
+
+    #[test]
+    fn matching() {
+        let solvers = [
+            solver(2025, 10),
+            solver(2025, 1),
+            solver(2025, 2),
+            solver(2024, 25),
+        ];
+        assert_eq!(select("1", &solvers), Ok(vec![(2025, 1)]));
+        assert_eq!(
+            select("1..10", &solvers),
+            Ok(vec![(2025, 1), (2025, 2), (2025, 10)])
+        );
+        assert_eq!(select("latest", &solvers), Ok(vec![(2025, 10)]));
+        assert_eq!(select("2024/latest", &solvers), Ok(vec![(2024, 25)]));
+        assert_eq!(select("all", &solvers).map(|s| s.len()), Ok(4));
+        assert_eq!(
+            select("2024/1", &solvers),
+            Err(SelectorError::UnknownDay(Some(2024), 1))
+        );
+    }
+}
@@ -1,9 +1,14 @@
 // Copied and extended from https://github.com/agubelu/AoC-rust-template/tree/master
 
+use crate::util::grid::Grid;
+use crate::util::ocr::ocr;
 use Solution::*;
+use num::{BigInt, BigUint};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Answer of a single part. Numeric variants compare equal when their values are, regardless of
+/// the width of the type.
+#[derive(Clone, Debug)]
 pub enum Solution {
     I8(i8),
     I16(i16),
@@ -17,13 +22,69 @@ pub enum Solution {
     U64(u64),
     U128(u128),
     Usize(usize),
+    BigInt(BigInt),
+    BigUint(BigUint),
     Str(String),
+    /// Drawing on a grid, see [`crate::util::ocr::ocr`] to read its letters.
+    Grid(Grid<bool>),
     Nothing(),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SolutionTuple(pub Solution, pub Solution);
 
+impl Solution {
+    /// Value of the numeric variants.
+    pub fn to_integer(&self) -> Option<BigInt> {
+        Some(match self {
+            I8(x) => (*x).into(),
+            I16(x) => (*x).into(),
+            I32(x) => (*x).into(),
+            I64(x) => (*x).into(),
+            I128(x) => (*x).into(),
+            Isize(x) => (*x).into(),
+            U8(x) => (*x).into(),
+            U16(x) => (*x).into(),
+            U32(x) => (*x).into(),
+            U64(x) => (*x).into(),
+            U128(x) => (*x).into(),
+            Usize(x) => (*x).into(),
+            Solution::BigInt(x) => x.clone(),
+            Solution::BigUint(x) => x.clone().into(),
+            Str(_) | Solution::Grid(_) | Nothing() => return None,
+        })
+    }
+
+    /// Compares against an expected answer, numerically for the numeric variants and by the
+    /// letters it reads for a grid.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Solution::Grid(grid) => ocr(grid).is_some_and(|text| text == expected),
+            _ => match self.to_integer() {
+                Some(value) => expected.parse::<BigInt>().is_ok_and(|e| e == value),
+                None => self.to_string() == expected,
+            },
+        }
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_integer(), other.to_integer()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (self, other) {
+                (Str(a), Str(b)) => a == b,
+                (Solution::Grid(a), Solution::Grid(b)) => a == b,
+                (Nothing(), Nothing()) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Solution {}
+
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -39,7 +100,10 @@ impl Display for Solution {
             U64(x) => x.fmt(f),
             U128(x) => x.fmt(f),
             Usize(x) => x.fmt(f),
+            Solution::BigInt(x) => x.fmt(f),
+            Solution::BigUint(x) => x.fmt(f),
             Str(x) => x.fmt(f),
+            Solution::Grid(x) => x.fmt(f),
             Nothing() => f.write_str("-"),
         }
     }
@@ -67,8 +131,16 @@ impl_from!(u32, U32);
 impl_from!(u64, U64);
 impl_from!(u128, U128);
 impl_from!(usize, Usize);
+impl_from!(BigInt, BigInt);
+impl_from!(BigUint, BigUint);
 impl_from!(String, Str);
 
+impl From<Grid<bool>> for Solution {
+    fn from(sol: Grid<bool>) -> Self {
+        Solution::Grid(sol)
+    }
+}
+
 impl From<&str> for Solution {
     fn from(sol: &str) -> Self {
         Str(sol.to_owned())
@@ -84,3 +156,21 @@ where
         SolutionTuple(value.0.into(), value.1.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_equality() {
+        assert_eq!(Solution::from(42u64), Solution::from(42usize));
+        assert_eq!(Solution::from(-3i8), Solution::from(BigInt::from(-3)));
+        assert_ne!(Solution::from(42u64), Solution::from("42"));
+        assert!(
+            Solution::from(BigUint::from(u128::MAX) * 2u8)
+                .matches("680564733841876926926749214863536422910")
+        );
+        assert!(Solution::from(7u8).matches("7"));
+        assert!(!Solution::from(7u8).matches("seven"));
+    }
+}
@@ -1,10 +1,28 @@
+use crate::infra::answers::Verification;
+use crate::infra::error::SolveError;
 use crate::infra::measure::MeasureContext;
-use crate::infra::solution::SolutionTuple;
+use crate::infra::params::Params;
+use crate::infra::selector::{Selector, SelectorError};
+use crate::infra::solution::{Solution, SolutionTuple};
 use linkme::distributed_slice;
 
+/// Year used by `register!` when none is given.
+pub const DEFAULT_YEAR: u16 = 2025;
+
 pub struct Solver {
+    pub year: u16,
     pub day: u8,
-    pub solve: fn(&mut MeasureContext, &str) -> SolutionTuple,
+    pub solve: Solve,
+}
+
+pub type SolveResult = Result<SolutionTuple, SolveError>;
+
+pub enum Solve {
+    /// Computes both parts at once. When a single part is requested, the `part1` or `part2`
+    /// span of the other one is left out of the measurements.
+    Both(fn(&mut MeasureContext, &str, &Params) -> SolveResult),
+    /// Shared preparation followed by the requested part, or both parts when `None`.
+    Parts(fn(&mut MeasureContext, &str, &Params, Option<u8>) -> SolveResult),
 }
 
 pub fn day_to_name(day: u8) -> String {
@@ -12,35 +30,229 @@ pub fn day_to_name(day: u8) -> String {
 }
 
 impl Solver {
-    pub const fn new(day: u8, solve: fn(&mut MeasureContext, &str) -> SolutionTuple) -> Self {
-        Solver { day, solve }
+    pub const fn new(year: u16, day: u8, solve: Solve) -> Self {
+        Solver { year, day, solve }
+    }
+
+    /// Solves the requested part, or both when `None`. Parts that are not computed are
+    /// [`Solution::Nothing`].
+    pub fn solve(
+        &self,
+        ctx: &mut MeasureContext,
+        input: &str,
+        params: &Params,
+        part: Option<u8>,
+    ) -> SolveResult {
+        match self.solve {
+            Solve::Both(solve) => {
+                let SolutionTuple(p1, p2) = solve(ctx, input, params)?;
+                match part {
+                    Some(1) => ctx.discard("part2"),
+                    Some(2) => ctx.discard("part1"),
+                    _ => {}
+                }
+                let filter = |n, solution| match part {
+                    Some(part) if part != n => Solution::Nothing(),
+                    _ => solution,
+                };
+                Ok(SolutionTuple(filter(1, p1), filter(2, p2)))
+            }
+            Solve::Parts(solve) => solve(ctx, input, params, part),
+        }
     }
 
+    pub fn examples(&self) -> impl Iterator<Item = &'static Example> {
+        EXAMPLES
+            .into_iter()
+            .filter(|e| e.year == self.year && e.day == self.day)
+    }
+
+    /// Name including the year, e.g. `2025/day05`.
     pub fn name(&self) -> String {
-        day_to_name(self.day)
+        format!("{}/{}", self.year, day_to_name(self.day))
     }
 }
 
 #[distributed_slice]
 pub static SOLVERS: [Solver];
 
+/// Registers a solver, the year defaults to [`DEFAULT_YEAR`]. Either takes a closure computing
+/// both parts, optionally taking the [`Params`] as third argument:
+///
+/// ```ignore
+/// register!(SOLVER, 1, |ctx, input| ctx.measure("all", || both(input)).into());
+/// ```
+///
+/// Or separate parts sharing an optional `prepare` stage, where part 2 may be omitted:
+///
+/// ```ignore
+/// register!(SOLVER, 2025, 3, prepare: prepare, part1: solve::<2>, part2: solve::<12>);
+/// ```
+///
+/// Prefixing the closure or the `prepare` stage with `try` makes it return a
+/// [`Result`] with a [`SolveError`]:
+///
+/// ```ignore
+/// register!(SOLVER, 5, try |ctx, input| Ok(both(ctx.measure("parse", || parse(input))?).into()));
+/// register!(SOLVER, 2, try prepare: prepare, part1: p1, part2: p2);
+/// ```
 #[macro_export]
 macro_rules! register {
-    ($id:ident, $day:literal, $solve:expr) => {
+    (@static $id:ident, $year:expr, $day:literal, $(prepare: $prepare:expr,)? $(try prepare: $try_prepare:expr,)? part1: $part1:expr $(, part2: $part2:expr)? $(,)?) => {
+        #[::linkme::distributed_slice($crate::infra::solver::SOLVERS)]
+        static $id: $crate::infra::solver::Solver = $crate::infra::solver::Solver::new(
+            $year,
+            $day,
+            $crate::infra::solver::Solve::Parts(|ctx, input, _params, part| {
+                let prepared = $crate::register!(@prepare ctx, input, [$($prepare)?], [$($try_prepare)?]);
+                Ok($crate::infra::solution::SolutionTuple(
+                    $crate::register!(@part ctx, part, 1, prepared, $part1),
+                    $crate::register!(@part ctx, part, 2, prepared $(, $part2)?),
+                ))
+            }),
+        );
+    };
+    (@static $id:ident, $year:expr, $day:literal, try |$ctx:pat_param, $input:pat_param| $solve:expr) => {
+        $crate::register!(@static $id, $year, $day, try |$ctx, $input, _params| $solve);
+    };
+    (@static $id:ident, $year:expr, $day:literal, try $solve:expr) => {
         #[::linkme::distributed_slice($crate::infra::solver::SOLVERS)]
-        static $id: $crate::infra::solver::Solver =
-            $crate::infra::solver::Solver::new($day, $solve);
+        static $id: $crate::infra::solver::Solver = $crate::infra::solver::Solver::new(
+            $year,
+            $day,
+            $crate::infra::solver::Solve::Both($solve),
+        );
+    };
+    (@static $id:ident, $year:expr, $day:literal, |$ctx:pat_param, $input:pat_param| $solve:expr) => {
+        $crate::register!(@static $id, $year, $day, |$ctx, $input, _params| $solve);
+    };
+    (@static $id:ident, $year:expr, $day:literal, |$ctx:pat_param, $input:pat_param, $params:pat_param| $solve:expr) => {
+        $crate::register!(@static $id, $year, $day, try |$ctx, $input, $params| Ok($solve));
+    };
+    (@prepare $ctx:ident, $input:ident, [], []) => {
+        $input
+    };
+    (@prepare $ctx:ident, $input:ident, [$prepare:expr], []) => {
+        $ctx.measure("prepare", || ($prepare)($input))
+    };
+    (@prepare $ctx:ident, $input:ident, [], [$prepare:expr]) => {
+        $ctx.measure("prepare", || ($prepare)($input))?
+    };
+    (@part $ctx:ident, $part:ident, $n:literal, $prepared:ident) => {
+        $crate::infra::solution::Solution::Nothing()
+    };
+    (@part $ctx:ident, $part:ident, $n:literal, $prepared:ident, $solve:expr) => {
+        if $part.is_none_or(|part| part == $n) {
+            $ctx.measure(concat!("part", $n), || ($solve)(&$prepared)).into()
+        } else {
+            $crate::infra::solution::Solution::Nothing()
+        }
+    };
+    ($id:ident, $year:literal, $day:literal, $($solve:tt)+) => {
+        $crate::register!(@static $id, $year, $day, $($solve)+);
+    };
+    ($id:ident, $day:literal, $($solve:tt)+) => {
+        $crate::register!(@static $id, $crate::infra::solver::DEFAULT_YEAR, $day, $($solve)+);
     };
 }
 
-pub fn match_solvers<S: AsRef<str>>(s: Option<S>) -> Vec<&'static Solver> {
-    let mut solvers: Vec<&Solver> = match s {
-        Some(d) => SOLVERS
-            .into_iter()
-            .filter(|s| s.name().contains(d.as_ref()))
-            .collect(),
-        None => SOLVERS.into_iter().collect(),
+/// Example input with its expected answers, see [`register_example!`].
+pub struct Example {
+    pub year: u16,
+    pub day: u8,
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    pub fn params(&self) -> Params {
+        self.params.into()
+    }
+
+    pub fn verify(&self, part: u8, solution: &Solution) -> Verification {
+        let expected = match part {
+            1 => self.part1,
+            _ => self.part2,
+        };
+        Verification::new(expected.map(str::to_string), solution)
+    }
+}
+
+#[distributed_slice]
+pub static EXAMPLES: [Example];
+
+/// Registers an example for a solver, the year defaults to [`DEFAULT_YEAR`]. Answers and
+/// parameter values are literals:
+///
+/// ```ignore
+/// register_example!(EXAMPLE, 8, input: EXAMPLE_INPUT, part1: 40, params: [connection_count = 10]);
+/// ```
+#[macro_export]
+macro_rules! register_example {
+    (@static $id:ident, $year:expr, $day:literal, input: $input:expr $(, part1: $part1:literal)? $(, part2: $part2:literal)? $(, params: [$($key:ident = $value:literal),* $(,)?])? $(,)?) => {
+        #[::linkme::distributed_slice($crate::infra::solver::EXAMPLES)]
+        static $id: $crate::infra::solver::Example = $crate::infra::solver::Example {
+            year: $year,
+            day: $day,
+            input: $input,
+            part1: $crate::register_example!(@answer $($part1)?),
+            part2: $crate::register_example!(@answer $($part2)?),
+            params: &[$($((stringify!($key), concat!($value))),*)?],
+        };
+    };
+    (@answer) => {
+        None
     };
-    solvers.sort_unstable_by_key(|s| s.day);
-    solvers
+    (@answer $answer:literal) => {
+        Some(concat!($answer))
+    };
+    ($id:ident, $year:literal, $day:literal, $($example:tt)+) => {
+        $crate::register_example!(@static $id, $year, $day, $($example)+);
+    };
+    ($id:ident, $day:literal, $($example:tt)+) => {
+        $crate::register_example!(@static $id, $crate::infra::solver::DEFAULT_YEAR, $day, $($example)+);
+    };
+}
+
+pub fn match_solvers(selector: &Selector) -> Result<Vec<&'static Solver>, SelectorError> {
+    selector.select(SOLVERS)
+}
+
+pub fn find_solver(year: u16, day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|s| s.year == year && s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            let solver = find_solver(example.year, example.day).unwrap();
+            let params = example.params();
+            let SolutionTuple(p1, p2) = solver
+                .solve(&mut MeasureContext::new(), example.input, &params, None)
+                .unwrap();
+            assert_eq!(
+                params.unused().collect::<Vec<_>>(),
+                [] as [&str; 0],
+                "{}: unused parameters",
+                solver.name()
+            );
+            for (part, solution) in [(1, p1), (2, p2)] {
+                let verification = example.verify(part, &solution);
+                assert!(
+                    !matches!(verification, Verification::Fail(_)),
+                    "{}/part{}: {} ({})",
+                    solver.name(),
+                    part,
+                    solution,
+                    verification
+                );
+            }
+        }
+    }
 }
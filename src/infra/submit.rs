@@ -0,0 +1,370 @@
+use crate::infra::input::authenticated;
+use crate::infra::solution::Solution;
+use crate::infra::solver::day_to_name;
+use crate::util::ocr::ocr;
+use num::BigInt;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const DEFAULT_ATTEMPTS_PATH: &str = "./input/attempts.toml";
+
+#[derive(Debug)]
+pub enum SubmitError {
+    MissingSessionToken,
+    /// Nothing to submit, or a grid without recognizable letters.
+    NoAnswer,
+    /// The answer was submitted before, with this outcome or an outcome ruling it out.
+    AlreadySubmitted(String, Outcome),
+    Io(PathBuf, std::io::Error),
+    Http(String, ureq::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::MissingSessionToken => {
+                f.write_str("missing AOC_SESSION_TOKEN environment variable")
+            }
+            SubmitError::NoAnswer => f.write_str("no answer to submit"),
+            SubmitError::AlreadySubmitted(answer, outcome) => {
+                write!(f, "{} is known to be {}", answer, outcome)
+            }
+            SubmitError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            SubmitError::Http(url, e) => write!(f, "{}: {}", url, e),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Response to a submitted answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with the time left to wait when given.
+    Wait(Option<Duration>),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// Recognizes the outcome from the article of the response page.
+    pub fn parse(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Outcome::Correct
+        } else if body.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if body.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if body.contains("You gave an answer too recently") {
+            Outcome::Wait(parse_wait(body))
+        } else if body.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether the outcome says something about the answer, and should be recorded.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wait(_) => "wait",
+            Outcome::WrongLevel => "wrong level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Wait(Some(remaining)) => {
+                write!(f, "wait {}s", remaining.as_secs())
+            }
+            outcome => f.write_str(outcome.name()),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Outcome::Correct,
+            Outcome::Wrong,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+        ]
+        .into_iter()
+        .find(|outcome| outcome.name() == s)
+        .ok_or(())
+    }
+}
+
+/// Parses "You have 1m 30s left to wait".
+fn parse_wait(body: &str) -> Option<Duration> {
+    let (before, _) = body.split_once(" left to wait")?;
+    let (_, remaining) = before.rsplit_once("You have ")?;
+    remaining
+        .split_whitespace()
+        .map(|part| {
+            let unit = match part.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            let value = part[..part.len() - 1].parse::<u64>().ok()?;
+            Some(value * unit)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Text to submit for a solution, reading the letters drawn by grid solutions.
+pub fn answer_text(solution: &Solution) -> Option<String> {
+    match solution {
+        Solution::Nothing() => None,
+        Solution::Grid(grid) => ocr(grid),
+        solution => Some(solution.to_string()),
+    }
+}
+
+/// Submitted answers with their outcome, keyed by year, day name and part:
+///
+/// ```toml
+/// [2025.day01.part1]
+/// 1034 = "too low"
+/// 6166 = "correct"
+/// ```
+#[derive(Debug, Default)]
+pub struct Attempts(toml::Table);
+
+impl Attempts {
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, SubmitError> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => content.parse().map(Attempts).map_err(|e| {
+                SubmitError::Io(
+                    path.to_path_buf(),
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+                )
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Attempts::default()),
+            Err(e) => Err(SubmitError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SubmitError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.0.to_string()).map_err(|e| SubmitError::Io(path.to_path_buf(), e))
+    }
+
+    fn part(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = (&str, Outcome)> {
+        self.0
+            .get(&year.to_string())
+            .and_then(|year| year.get(day_to_name(day)))
+            .and_then(|day| day.get(format!("part{}", part)))
+            .and_then(toml::Value::as_table)
+            .into_iter()
+            .flatten()
+            .filter_map(|(answer, outcome)| {
+                Some((answer.as_str(), outcome.as_str()?.parse().ok()?))
+            })
+    }
+
+    /// Outcome of an earlier attempt that rules out the answer, taking the too high and too low
+    /// bounds into account for numeric answers.
+    pub fn known(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<Outcome> {
+        let attempts = self.part(year, day, part).collect::<Vec<_>>();
+        if let Some((_, outcome)) = attempts.iter().find(|(attempt, _)| *attempt == answer) {
+            return Some(*outcome);
+        }
+        let number = answer.parse::<BigInt>().ok();
+        attempts.into_iter().find_map(|(attempt, outcome)| {
+            let attempt = attempt.parse::<BigInt>().ok();
+            match (outcome, &number, attempt) {
+                (Outcome::Correct, _, _) => Some(Outcome::Wrong),
+                (Outcome::TooHigh, Some(number), Some(attempt)) if *number >= attempt => {
+                    Some(outcome)
+                }
+                (Outcome::TooLow, Some(number), Some(attempt)) if *number <= attempt => {
+                    Some(outcome)
+                }
+                _ => None,
+            }
+        })
+    }
+
+    pub fn record(&mut self, year: u16, day: u8, part: u8, answer: &str, outcome: Outcome) {
+        let mut table = &mut self.0;
+        for key in [year.to_string(), day_to_name(day), format!("part{}", part)] {
+            let value = table
+                .entry(key)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            let toml::Value::Table(inner) = value else {
+                return;
+            };
+            table = inner;
+        }
+        table.insert(
+            answer.to_string(),
+            toml::Value::String(outcome.name().to_string()),
+        );
+    }
+}
+
+/// Posts answers using the session token, to adventofcode.com or a server mimicking it.
+pub struct Submitter {
+    base_url: String,
+    session_token: Option<String>,
+}
+
+impl Submitter {
+    pub fn new(base_url: impl Into<String>, session_token: Option<String>) -> Self {
+        Submitter {
+            base_url: base_url.into(),
+            session_token,
+        }
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Outcome, SubmitError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let mut response = authenticated(ureq::post(&url), self.session_token.as_deref())
+            .ok_or(SubmitError::MissingSessionToken)?
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .map_err(|e| SubmitError::Http(url.clone(), e))?;
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| SubmitError::Http(url.clone(), e))?;
+        Ok(Outcome::parse(&body))
+    }
+
+    /// Submits the answer unless an earlier attempt rules it out, recording the outcome.
+    pub fn submit_once(
+        &self,
+        attempts: &mut Attempts,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Outcome, SubmitError> {
+        if let Some(outcome) = attempts.known(year, day, part, answer) {
+            return Err(SubmitError::AlreadySubmitted(answer.to_string(), outcome));
+        }
+        let outcome = self.submit(year, day, part, answer)?;
+        if outcome.is_final() {
+            attempts.record(year, day, part, answer, outcome);
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::mock_server;
+
+    #[test]
+    fn outcome() {
+        assert_eq!(
+            Outcome::parse("<article><p>That's the right answer! You are one gold star"),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer. If you're stuck"),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse("You gave an answer too recently. You have 1m 5s left to wait."),
+            Outcome::Wait(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse("You don't seem to be solving the right level."),
+            Outcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn attempts() {
+        let mut attempts = Attempts::default();
+        attempts.record(2025, 1, 1, "100", Outcome::TooHigh);
+        attempts.record(2025, 1, 1, "10", Outcome::TooLow);
+        attempts.record(2025, 1, 1, "abc", Outcome::Wrong);
+        let attempts = Attempts(attempts.0.to_string().parse().unwrap());
+
+        assert_eq!(attempts.known(2025, 1, 1, "100"), Some(Outcome::TooHigh));
+        assert_eq!(attempts.known(2025, 1, 1, "150"), Some(Outcome::TooHigh));
+        assert_eq!(attempts.known(2025, 1, 1, "3"), Some(Outcome::TooLow));
+        assert_eq!(attempts.known(2025, 1, 1, "abc"), Some(Outcome::Wrong));
+        assert_eq!(attempts.known(2025, 1, 1, "50"), None);
+        assert_eq!(attempts.known(2025, 1, 2, "100"), None);
+    }
+
+    #[test]
+    fn submit() {
+        let (base_url, request) = mock_server(
+            "200 OK",
+            "<article><p>That's the right answer!</p></article>",
+        );
+        let submitter = Submitter::new(base_url, Some("token".to_string()));
+        let mut attempts = Attempts::default();
+        assert_eq!(
+            submitter
+                .submit_once(&mut attempts, 2025, 5, 2, "42")
+                .unwrap(),
+            Outcome::Correct
+        );
+
+        let request = request();
+        assert!(request.starts_with("POST /2025/day/5/answer "));
+        assert!(request.contains("session=token"));
+        assert!(request.ends_with("level=2&answer=42"));
+
+        // The server is gone, a second submission must not reach it.
+        assert!(matches!(
+            submitter.submit_once(&mut attempts, 2025, 5, 2, "42"),
+            Err(SubmitError::AlreadySubmitted(_, Outcome::Correct))
+        ));
+        assert!(matches!(
+            submitter.submit_once(&mut attempts, 2025, 5, 2, "43"),
+            Err(SubmitError::AlreadySubmitted(_, Outcome::Wrong))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+//! Helpers shared by the tests of the infra modules.
+
+use crate::infra::measure::Statistics;
+use crate::infra::report::Report;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Serves a single request, returning the base URL and a function waiting for the request,
+/// including its body.
+pub fn mock_server(status: &'static str, body: &'static str) -> (String, impl FnOnce() -> String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        let mut reader = BufReader::new(&stream);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+        }
+        let mut content = vec![0; length];
+        reader.read_exact(&mut content).unwrap();
+        request.push_str(&String::from_utf8(content).unwrap());
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+        request
+    });
+    (base_url, move || handle.join().unwrap())
+}
+
+/// Empty directory that is removed when dropped, also when the test fails.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "aoc-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Report of `2025/day05` without parts, with a single sample of the duration and of each
+/// measurement in nanoseconds.
+pub fn report(duration: u64, measurements: &[(&str, u64)]) -> Report {
+    let stats = |nanos| Statistics::from_samples(&[Duration::from_nanos(nanos)], false);
+    Report {
+        name: "2025/day05".to_string(),
+        year: 2025,
+        day: 5,
+        parts: vec![],
+        duration: stats(duration),
+        cpu_time: Duration::from_nanos(duration),
+        measurements: measurements
+            .iter()
+            .map(|(label, nanos)| (label.to_string(), stats(*nanos)))
+            .collect(),
+        spans: vec![],
+        baseline: vec![],
+    }
+}
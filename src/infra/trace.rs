@@ -0,0 +1,104 @@
+use crate::infra::measure::MeasureContext;
+use serde_json::{Value, json};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Spans of all runs in the Chrome Trace Event format, as read by Perfetto and speedscope. Each
+/// run gets its own track, containing its iterations with their spans.
+pub struct Trace {
+    start: Instant,
+    events: Vec<Value>,
+}
+
+impl Trace {
+    /// Timestamps are relative to the start.
+    pub fn new(start: Instant) -> Self {
+        Trace {
+            start,
+            events: vec![],
+        }
+    }
+
+    pub fn record(&mut self, track: usize, name: &str, ctx: &MeasureContext) {
+        let micros = |duration: Duration| duration.as_nanos() as f64 / 1000.0;
+        let event = |name: &str, start: Instant, end: Instant| {
+            json!({
+                "name": name,
+                "cat": "solver",
+                "ph": "X",
+                "ts": micros(start.duration_since(self.start)),
+                "dur": micros(end.duration_since(start)),
+                "pid": 1,
+                "tid": track,
+            })
+        };
+
+        self.events.push(json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": 1,
+            "tid": track,
+            "args": { "name": name },
+        }));
+        for (i, (start, end)) in ctx.iteration_bounds().into_iter().enumerate() {
+            let mut iteration = event(name, start, end);
+            iteration["args"] = json!({ "iteration": i });
+            self.events.push(iteration);
+        }
+        self.events.extend(
+            ctx.spans()
+                .map(|(label, start, end)| event(label, start, end)),
+        );
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "traceEvents": self.events,
+            "displayTimeUnit": "ns",
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut out = BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(&mut out, &self.to_json())?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events() {
+        let mut ctx = MeasureContext::new();
+        let mut trace = Trace::new(Instant::now());
+        for _ in 0..2 {
+            ctx.start_iteration();
+            ctx.span("outer", |ctx| ctx.measure("inner", || ()));
+        }
+        trace.record(3, "2025/day01", &ctx);
+
+        let json = trace.to_json();
+        let events = json["traceEvents"].as_array().unwrap();
+        let names = events
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "thread_name",
+                "2025/day01",
+                "2025/day01",
+                "outer",
+                "inner",
+                "outer",
+                "inner"
+            ]
+        );
+        assert!(events.iter().all(|e| e["tid"] == 3));
+        assert!(events[3]["dur"].as_f64().unwrap() >= events[4]["dur"].as_f64().unwrap());
+    }
+}
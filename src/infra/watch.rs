@@ -0,0 +1,248 @@
+use crate::infra::baseline::Comparison;
+use indexmap::IndexMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Time between two checks of the watched files.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls the modification time of files, a missing file counts as a file without one.
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let time = modified(&path);
+                    (path, time)
+                })
+                .collect(),
+        }
+    }
+
+    /// Files changed since the previous call or since the watcher was created.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        self.files
+            .iter_mut()
+            .filter_map(|(path, time)| {
+                let current = modified(path);
+                (current != *time).then(|| {
+                    *time = current;
+                    path.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Blocks until at least one of the files changed.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+    }
+}
+
+/// Answers and median timings per solver, read from the JSON report of a run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot(IndexMap<String, SolverSnapshot>);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SolverSnapshot {
+    answers: IndexMap<String, String>,
+    timings: IndexMap<String, Duration>,
+}
+
+/// Difference of a solver between two runs.
+#[derive(Clone, Debug)]
+pub enum Change {
+    Answer {
+        name: String,
+        part: String,
+        previous: Option<String>,
+        current: Option<String>,
+    },
+    Timing {
+        name: String,
+        comparison: Comparison,
+    },
+}
+
+impl Snapshot {
+    pub fn parse(json: &str) -> serde_json::Result<Self> {
+        let reports: Vec<serde_json::Value> = serde_json::from_str(json)?;
+        let nanos =
+            |value: &serde_json::Value, key| value.get(key)?.as_u64().map(Duration::from_nanos);
+        Ok(Snapshot(
+            reports
+                .iter()
+                .filter_map(|report| {
+                    let name = report.get("name")?.as_str()?.to_string();
+                    let answers = ["part1", "part2"]
+                        .into_iter()
+                        .filter_map(|part| {
+                            let answer = report.get(part)?.as_str()?;
+                            Some((part.to_string(), answer.to_string()))
+                        })
+                        .collect();
+                    let timings = nanos(report, "median_ns")
+                        .map(|time| ("time".to_string(), time))
+                        .into_iter()
+                        .chain(report.get("measurements")?.as_array()?.iter().filter_map(
+                            |measurement| {
+                                let label = measurement.get("label")?.as_str()?;
+                                Some((label.to_string(), nanos(measurement, "median_ns")?))
+                            },
+                        ))
+                        .collect();
+                    Some((name, SolverSnapshot { answers, timings }))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Changed answers and all timings of the solvers in both snapshots, a slowdown above the
+    /// threshold percentage is flagged as a regression.
+    pub fn diff(&self, previous: &Snapshot, threshold: f64) -> Vec<Change> {
+        let mut changes = vec![];
+        for (name, current) in &self.0 {
+            let Some(previous) = previous.0.get(name) else {
+                continue;
+            };
+            for part in ["part1", "part2"] {
+                let (before, after) = (previous.answers.get(part), current.answers.get(part));
+                if before != after {
+                    changes.push(Change::Answer {
+                        name: name.clone(),
+                        part: part.to_string(),
+                        previous: before.cloned(),
+                        current: after.cloned(),
+                    });
+                }
+            }
+            for (label, time) in &current.timings {
+                let Some(baseline) = previous.timings.get(label) else {
+                    continue;
+                };
+                let mut comparison = Comparison {
+                    label: label.clone(),
+                    baseline: (*baseline).max(Duration::from_nanos(1)),
+                    current: *time,
+                    regression: false,
+                };
+                comparison.regression = comparison.change() > threshold;
+                changes.push(Change::Timing {
+                    name: name.clone(),
+                    comparison,
+                });
+            }
+        }
+        changes
+    }
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, solver) in &self.0 {
+            for (part, answer) in &solver.answers {
+                writeln!(f, "{}/{}: {}", name, part, answer)?;
+            }
+            if let Some(time) = solver.timings.get("time") {
+                writeln!(f, "{}/time: {:?}", name, time)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Answer {
+                name,
+                part,
+                previous,
+                current,
+            } => write!(
+                f,
+                "{}/{}: {} -> {} (changed)",
+                name,
+                part,
+                previous.as_deref().unwrap_or("-"),
+                current.as_deref().unwrap_or("-")
+            ),
+            Change::Timing { name, comparison } => write!(
+                f,
+                "{}/diff/{}: {:?} -> {:?} ({:+.1}%){}",
+                name,
+                comparison.label,
+                comparison.baseline,
+                comparison.current,
+                comparison.change(),
+                if comparison.regression {
+                    " REGRESSION"
+                } else {
+                    ""
+                }
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::testing::TempDir;
+
+    #[test]
+    fn diff() {
+        let previous = Snapshot::parse(
+            r#"[{"name":"2025/day05","median_ns":2000,"part1":"1","part2":"2","measurements":[{"label":"parse","median_ns":1000}]}]"#,
+        )
+        .unwrap();
+        let current = Snapshot::parse(
+            r#"[{"name":"2025/day05","median_ns":1000,"part1":"1","part2":"3","measurements":[{"label":"parse","median_ns":1500}]}]"#,
+        )
+        .unwrap();
+
+        let changes = current
+            .diff(&previous, 10.0)
+            .iter()
+            .map(Change::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                "2025/day05/part2: 2 -> 3 (changed)",
+                "2025/day05/diff/time: 2µs -> 1µs (-50.0%)",
+                "2025/day05/diff/parse: 1µs -> 1.5µs (+50.0%) REGRESSION",
+            ]
+        );
+        assert!(current.diff(&Snapshot::default(), 10.0).is_empty());
+    }
+
+    #[test]
+    fn watcher() {
+        let directory = TempDir::new("watch");
+        let path = directory.join("input.txt");
+        let mut watcher = Watcher::new([path.clone()]);
+        assert!(watcher.changed().is_empty());
+        std::fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.changed(), std::slice::from_ref(&path));
+        assert!(watcher.changed().is_empty());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), [path]);
+    }
+}
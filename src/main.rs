@@ -1,75 +1,783 @@
-use aoc_2025::infra::input::read_input;
-use aoc_2025::infra::measure::MeasureContext;
+use aoc_2025::infra::allocations::{self, AllocStats};
+use aoc_2025::infra::answers::{Answers, DEFAULT_ANSWERS_PATH, Verification, load_expected};
+use aoc_2025::infra::baseline::Baseline;
+use aoc_2025::infra::input::{
+    BatchInput, CachedProvider, DEFAULT_BASE_URL, DEFAULT_INPUT_DIRECTORY, DirectoryProvider,
+    HttpProvider, InputProvider, LAST_REQUEST_FILE, read_input_file,
+};
+use aoc_2025::infra::measure::{MeasureContext, Statistics};
+use aoc_2025::infra::params::{Params, parse_param};
+use aoc_2025::infra::perf::{self, PerfCounters, PerfStats};
+use aoc_2025::infra::report::{Format, PartReport, Report, Reporter, SpanReport, Totals};
+use aoc_2025::infra::runner::{execute, run_ordered, thread_cpu_time};
+use aoc_2025::infra::scaffold::{DAY_DIRECTORY, create_day};
+use aoc_2025::infra::selector::Selector;
 use aoc_2025::infra::solution::SolutionTuple;
-use aoc_2025::infra::solver::match_solvers;
-use clap::Parser;
-use std::hint::black_box;
-use std::time::Duration;
+use aoc_2025::infra::solver::{Solver, day_to_name, match_solvers};
+use aoc_2025::infra::submit::{
+    Attempts, DEFAULT_ATTEMPTS_PATH, Outcome, SubmitError, Submitter, answer_text,
+};
+use aoc_2025::infra::trace::Trace;
+use aoc_2025::infra::watch::{Snapshot, Watcher};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    args_override_self = true
+)]
 struct Args {
-    /// Day
-    day: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Days to run, e.g. `3`, `3..7`, `1,4,8`, `5:2` for part 2 only, `all`, `latest`,
+    /// optionally prefixed by the year like `2025/5`
+    #[arg(default_value = "all")]
+    day: Selector,
     #[arg(short, long, default_value = "1")]
     repeat: u32,
     #[arg(short, long, default_value = "0")]
     warmup: u32,
+    /// Number of solvers to run in parallel, 0 uses all available cores
+    #[arg(short, long, default_value = "1")]
+    jobs: usize,
+    /// Reject outlier iterations from the timing statistics
+    #[arg(long)]
+    reject_outliers: bool,
+    /// Read the input of the selected day from this file, or from stdin for `-`
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    input: Option<String>,
+    #[command(flatten)]
+    inputs: InputArgs,
+    /// Solver parameter, overriding the default or the example value
+    #[arg(short, long = "param", value_name = "KEY=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    /// Run the registered examples instead of the inputs, verifying their answers
+    #[arg(long)]
+    example: bool,
+    /// Verify the answers of the --input run against the part1 and part2 keys of this TOML file
+    #[arg(long, value_name = "PATH", requires = "input")]
+    expected: Option<String>,
+    /// Run every YYYY/dayNN/*.txt input in the input directory, verifying the answers in the
+    /// sibling .toml files, and show the results as a table. Each input runs in its own process,
+    /// so a solver failing on one input does not stop the others
+    #[arg(long, conflicts_with_all = ["example", "input"])]
+    batch: bool,
+    /// Compare the answers against the answers file
+    #[arg(long)]
+    verify: bool,
+    #[arg(long, default_value = DEFAULT_ANSWERS_PATH)]
+    answers: String,
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+    /// Store the median timings under this name
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+    /// Compare the median timings against a saved baseline
+    #[arg(long, value_name = "NAME")]
+    baseline: Option<String>,
+    /// Slowdown in percent above which a label is flagged as a regression
+    #[arg(long, default_value = "10")]
+    regression_threshold: f64,
+    /// Write all measured spans to this file in the Chrome Trace Event format
+    #[arg(long, value_name = "PATH", conflicts_with = "batch")]
+    trace: Option<String>,
+    /// Rebuild and run again whenever the source or input of a selected day changes, showing
+    /// the changed answers and timings
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct InputArgs {
+    /// Directory containing the YYYY/dayNN.txt inputs
+    #[arg(long, default_value = DEFAULT_INPUT_DIRECTORY)]
+    input_dir: String,
+    /// Never download missing inputs
+    #[arg(long)]
+    offline: bool,
+    /// Download the inputs again, replacing the stored files
+    #[arg(long, conflicts_with = "offline")]
+    refresh_input: bool,
+    /// Wait for puzzles that have not unlocked yet instead of failing
+    #[arg(long)]
+    wait_for_unlock: bool,
+    /// Minimum number of seconds between two input downloads
+    #[arg(long, default_value = "5")]
+    request_interval: f64,
+    /// Server to download missing inputs from
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+impl InputArgs {
+    fn provider(&self) -> Box<dyn InputProvider> {
+        if self.offline {
+            Box::new(DirectoryProvider::new(&self.input_dir))
+        } else {
+            Box::new(
+                CachedProvider::new(
+                    DirectoryProvider::new(&self.input_dir),
+                    HttpProvider::new(&self.base_url, std::env::var("AOC_SESSION_TOKEN").ok())
+                        .with_min_interval(Duration::from_secs_f64(self.request_interval))
+                        .with_wait_for_unlock(self.wait_for_unlock)
+                        .with_last_request_file(Path::new(&self.input_dir).join(LAST_REQUEST_FILE)),
+                )
+                .with_refresh(self.refresh_input),
+            )
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create src/day/dayNN.rs from the day00 template and declare it in src/day/mod.rs
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// File containing the example input to fill in
+        #[arg(long, value_name = "PATH")]
+        example: Option<String>,
+    },
+    /// Solve a part and submit its answer, unless an earlier attempt already ruled it out
+    Submit {
+        /// Day to submit, optionally prefixed by the year like `2025/5`
+        day: Selector,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// File recording the submitted answers and their outcome
+        #[arg(long, default_value = DEFAULT_ATTEMPTS_PATH)]
+        attempts: String,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+}
+
+fn new_day(day: u8, example: Option<&str>) -> ExitCode {
+    let example = match example.map(std::fs::read_to_string).transpose() {
+        Ok(example) => example,
+        Err(e) => {
+            eprintln!("Unable to read example: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match create_day(DAY_DIRECTORY.as_ref(), day, example.as_deref()) {
+        Ok(path) => {
+            println!("Created {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            eprintln!("Day {} already exists", day);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Unable to create day {}: {}", day, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(selector: &Selector, part: u8, attempts_path: &str, inputs: &InputArgs) -> ExitCode {
+    let solver = match match_solvers(selector).as_deref() {
+        Ok([solver]) => *solver,
+        Ok(_) => {
+            eprintln!("Select a single day to submit");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut attempts = match Attempts::load_or_default(attempts_path) {
+        Ok(attempts) => attempts,
+        Err(e) => {
+            eprintln!("Unable to load attempts: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match inputs.provider().input(solver.year, solver.day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Unable to read input: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let solution = match execute(solver, &input, &Params::default(), Some(part), 0, 1) {
+        Ok((solution, _)) => [solution.0, solution.1][part as usize - 1].clone(),
+        Err(e) => {
+            eprintln!("{}: {}", solver.name(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(answer) = answer_text(&solution) else {
+        eprintln!("{} part {}: {}", solver.name(), part, SubmitError::NoAnswer);
+        return ExitCode::FAILURE;
+    };
+    println!("{} part {}: submitting {}", solver.name(), part, answer);
+
+    let submitter = Submitter::new(&inputs.base_url, std::env::var("AOC_SESSION_TOKEN").ok());
+    let result = submitter.submit_once(&mut attempts, solver.year, solver.day, part, &answer);
+    if let Err(e) = attempts.save(attempts_path) {
+        eprintln!("Unable to save attempts: {}", e);
+        return ExitCode::FAILURE;
+    }
+    match result {
+        Ok(Outcome::Correct) => {
+            println!("Correct");
+            ExitCode::SUCCESS
+        }
+        Ok(Outcome::Wait(Some(remaining))) => {
+            eprintln!("Answered too recently, wait {}s", remaining.as_secs());
+            ExitCode::FAILURE
+        }
+        Ok(outcome) => {
+            eprintln!("Not accepted: {}", outcome);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Not submitted: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the selected solvers through `cargo run` with the same profile, features and arguments,
+/// again whenever one of their source or input files changes.
+fn watch(args: &Args) -> ExitCode {
+    let solvers = match match_solvers(&args.day) {
+        Ok(solvers) => solvers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.input.as_deref() == Some("-") {
+        eprintln!("Unable to watch stdin, pass the input as a file instead");
+        return ExitCode::FAILURE;
+    }
+    let inputs = DirectoryProvider::new(&args.inputs.input_dir);
+    let mut watcher = Watcher::new(solvers.iter().flat_map(|solver| {
+        [
+            Path::new(DAY_DIRECTORY).join(format!("{}.rs", day_to_name(solver.day))),
+            match &args.input {
+                Some(path) => PathBuf::from(path),
+                None => inputs.path(solver.year, solver.day),
+            },
+        ]
+    }));
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut cargo_args = vec!["run", "--quiet"];
+    if !cfg!(debug_assertions) {
+        cargo_args.push("--release");
+    }
+    let features = [
+        (allocations::ENABLED, "count-allocations"),
+        (perf::ENABLED, "perf-counters"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, feature)| enabled.then_some(feature))
+    .collect::<Vec<_>>()
+    .join(",");
+    if !features.is_empty() {
+        cargo_args.extend(["--features", &features]);
+    }
+    cargo_args.push("--");
+    let run_args = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .chain(["--format".to_string(), "json".to_string()])
+        .collect::<Vec<_>>();
+
+    // Variables Cargo sets for this binary, build scripts tracking them would rebuild otherwise.
+    let run_env = std::env::vars()
+        .map(|(key, _)| key)
+        .filter(|key| {
+            [
+                "CARGO_PKG_",
+                "CARGO_MANIFEST_",
+                "CARGO_CRATE_NAME",
+                "CARGO_BIN_NAME",
+                "CARGO_PRIMARY_PACKAGE",
+            ]
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+        })
+        .collect::<Vec<_>>();
+
+    let mut previous = None;
+    loop {
+        let mut command = std::process::Command::new(&cargo);
+        for key in &run_env {
+            command.env_remove(key);
+        }
+        match command
+            .args(&cargo_args)
+            .args(&run_args)
+            .stderr(std::process::Stdio::inherit())
+            .output()
+        {
+            Ok(output) => match Snapshot::parse(&String::from_utf8_lossy(&output.stdout)) {
+                Ok(snapshot) => {
+                    print!("{}", snapshot);
+                    if let Some(previous) = &previous {
+                        for change in snapshot.diff(previous, args.regression_threshold) {
+                            println!("{}", change);
+                        }
+                    }
+                    previous = Some(snapshot);
+                }
+                Err(_) => eprintln!("Run failed: {}", output.status),
+            },
+            Err(e) => {
+                eprintln!("Unable to run {}: {}", cargo, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        eprintln!("Watching for changes...");
+        for path in watcher.wait() {
+            eprintln!("Changed: {}", path.display());
+        }
+    }
 }
 
-fn main() {
+struct Run {
+    solver: &'static Solver,
+    name: String,
+    /// Input to solve, or why it could not be read, which fails only this run.
+    input: Result<String, String>,
+    /// Input file to solve in a child process instead, see [`solve_in_child`].
+    path: Option<PathBuf>,
+    params: Params,
+    /// Answers to verify against instead of the answers file.
+    expected: Option<[Option<String>; 2]>,
+}
+
+fn new_report(
+    run: &Run,
+    solution: SolutionTuple,
+    ctx: &MeasureContext,
+    cpu_time: Duration,
+    args: &Args,
+    answers: Option<&Answers>,
+) -> Report {
+    let parts = [(1, solution.0), (2, solution.1)]
+        .into_iter()
+        .filter(|(part, _)| args.day.includes_part(*part))
+        .map(|(part, solution)| PartReport {
+            part,
+            verification: match &run.expected {
+                Some(expected) => Some(Verification::new(
+                    expected[part as usize - 1].clone(),
+                    &solution,
+                )),
+                None => answers.map(|answers| answers.verify(&run.solver.name(), part, &solution)),
+            },
+            solution,
+        })
+        .collect();
+    Report {
+        name: run.name.clone(),
+        year: run.solver.year,
+        day: run.solver.day,
+        parts,
+        duration: Statistics::from_samples(&ctx.iteration_durations(), args.reject_outliers),
+        cpu_time,
+        measurements: ctx
+            .samples()
+            .into_iter()
+            .map(|(label, samples)| {
+                (
+                    label.to_string(),
+                    Statistics::from_samples(&samples, args.reject_outliers),
+                )
+            })
+            .collect(),
+        spans: ctx
+            .tree()
+            .into_iter()
+            .map(|node| SpanReport {
+                label: node.label.to_string(),
+                depth: node.depth,
+                total: Statistics::from_samples(&node.total, args.reject_outliers),
+                self_time: Statistics::from_samples(&node.self_time, args.reject_outliers),
+                allocations: allocations::ENABLED.then(|| AllocStats::average(&node.allocations)),
+                counters: ctx
+                    .has_counters()
+                    .then(|| PerfStats::average(&node.counters)),
+            })
+            .collect(),
+        baseline: vec![],
+    }
+}
+
+/// Solves the input file in a child process running this executable, so that a solver panicking
+/// on one of the batch inputs only fails that input, also when panics abort. Returns the
+/// diagnostics of the child with the report it printed.
+fn solve_in_child(run: &Run, path: &Path, args: &Args) -> (String, Result<Report, String>) {
+    let mut command = match std::env::current_exe() {
+        Ok(executable) => std::process::Command::new(executable),
+        Err(e) => {
+            return (
+                String::new(),
+                Err(format!("unable to find the executable: {}", e)),
+            );
+        }
+    };
+    let part = args
+        .day
+        .part
+        .map(|part| format!(":{}", part))
+        .unwrap_or_default();
+    command
+        .arg(format!("{}/{}{}", run.solver.year, run.solver.day, part))
+        .arg("--input")
+        .arg(path)
+        .arg("--expected")
+        .arg(path.with_extension("toml"))
+        .arg("--format=json")
+        .arg(format!("--repeat={}", args.repeat))
+        .arg(format!("--warmup={}", args.warmup));
+    if args.reject_outliers {
+        command.arg("--reject-outliers");
+    }
+    for (key, value) in &args.params {
+        command.arg(format!("--param={}={}", key, value));
+    }
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(e) => return (String::new(), Err(format!("unable to start: {}", e))),
+    };
+
+    // The summaries of the child are replaced by the ones over all inputs.
+    let diagnostics = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| !line.starts_with("Verification: ") && !line.starts_with("Errors: "))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let report = serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout)
+        .ok()
+        .and_then(|records| Report::from_json(records.first()?));
+    let result = match report {
+        Some(report) => Ok(Report {
+            name: run.name.clone(),
+            ..report
+        }),
+        None => Err(format!("solver failed ({})", output.status)),
+    };
+    (diagnostics, result)
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
+    match &args.command {
+        Some(Command::New { day, example }) => return new_day(*day, example.as_deref()),
+        Some(Command::Submit {
+            day,
+            part,
+            attempts,
+            inputs,
+        }) => return submit(day, *part, attempts, inputs),
+        None => {}
+    }
     assert!(args.repeat > 0);
+    if args.watch {
+        return watch(&args);
+    }
 
-    let solvers = match_solvers(args.day.as_ref());
+    let answers = if args.verify {
+        match Answers::load(&args.answers) {
+            Ok(answers) => Some(answers),
+            Err(e) => {
+                eprintln!("Unable to load answers from {}: {}", args.answers, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+    let mut verifications = vec![];
 
-    let inputs: Vec<_> = solvers.iter().map(|s| read_input(s.day)).collect();
+    let baseline = match args.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Unable to load baseline: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut saved_baseline = match args
+        .save_baseline
+        .as_deref()
+        .map(Baseline::load_or_default)
+        .transpose()
+    {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Unable to load baseline: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if perf::ENABLED
+        && let Err(e) = PerfCounters::open()
+    {
+        eprintln!("Hardware counters unavailable: {}", e);
+    }
 
-    let mut total_duration = Duration::default();
-    for (solver, input) in solvers.into_iter().zip(inputs) {
-        let name = solver.name();
-        {
-            let mut ctx = MeasureContext::new();
-            for _ in 0..args.warmup {
-                black_box((solver.solve)(&mut ctx, black_box(&input)));
+    let mut regressions = 0;
+    let mut errors = 0;
+
+    let solvers = match match_solvers(&args.day) {
+        Ok(solvers) => solvers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = if args.example {
+        solvers
+            .into_iter()
+            .flat_map(|solver| {
+                solver.examples().enumerate().map(move |(i, example)| Run {
+                    solver,
+                    name: match i {
+                        0 => format!("{}/example", solver.name()),
+                        i => format!("{}/example{}", solver.name(), i + 1),
+                    },
+                    input: Ok(example.input.to_string()),
+                    path: None,
+                    params: example.params(),
+                    expected: Some([example.part1, example.part2].map(|p| p.map(str::to_string))),
+                })
+            })
+            .collect::<Vec<_>>()
+    } else if args.batch {
+        let inputs = DirectoryProvider::new(&args.inputs.input_dir);
+        let mut runs = vec![];
+        for solver in solvers {
+            let batch = match inputs.batch(solver.year, solver.day) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    eprintln!("Unable to read input: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            for BatchInput { name, path } in batch {
+                runs.push(Run {
+                    solver,
+                    name: format!("{}/{}", solver.name(), name),
+                    input: Ok(String::new()),
+                    path: Some(path),
+                    params: Params::default(),
+                    expected: None,
+                });
             }
         }
+        if runs.is_empty() {
+            eprintln!(
+                "No inputs found in {}/YYYY/dayNN/*.txt",
+                args.inputs.input_dir
+            );
+            return ExitCode::FAILURE;
+        }
+        runs
+    } else if let Some(path) = &args.input {
+        let [solver] = solvers[..] else {
+            eprintln!("Select a single day to run on {}", path);
+            return ExitCode::FAILURE;
+        };
+        let input = match read_input_file(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Unable to read input: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let expected = match args.expected.as_deref().map(load_expected).transpose() {
+            Ok(expected) => expected,
+            Err(e) => {
+                eprintln!("Unable to load answers: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let source = match path.as_str() {
+            "-" => "stdin",
+            path => Path::new(path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(path),
+        };
+        vec![Run {
+            solver,
+            name: format!("{}/{}", solver.name(), source),
+            input: Ok(input),
+            path: None,
+            params: Params::default(),
+            expected,
+        }]
+    } else {
+        let provider = args.inputs.provider();
+        solvers
+            .into_iter()
+            .map(|solver| Run {
+                solver,
+                name: solver.name(),
+                input: provider
+                    .input(solver.year, solver.day)
+                    .map_err(|e| format!("Unable to read input: {}", e)),
+                path: None,
+                params: Params::default(),
+                expected: None,
+            })
+            .collect()
+    };
+    let run_count = runs.len();
+
+    let jobs = match args.jobs {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    };
+    let runs = runs
+        .into_iter()
+        .map(|mut run| {
+            run.params.extend(args.params.iter().cloned());
+            run
+        })
+        .collect::<Vec<_>>();
 
-        let mut ctx = MeasureContext::with_capacity(3 * args.repeat as usize);
-        let solution = (solver.solve)(&mut ctx, black_box(&input));
-        for _ in 0..args.repeat - 1 {
-            assert_eq!(&(solver.solve)(&mut ctx, black_box(&input)), &solution);
-        }
-
-        let SolutionTuple(p1, p2) = solution;
-
-        println!("{}/part1: {}", name, p1);
-        println!("{}/part2: {}", name, p2);
-
-        let per_iter_duration = ctx.duration() / args.repeat;
-        println!(
-            "{}/time: {:?}{}",
-            name,
-            per_iter_duration,
-            if ctx.measurements().into_iter().next().is_none() {
-                "".to_string()
-            } else {
-                format!(
-                    " ({})",
-                    ctx.measurements()
-                        .into_iter()
-                        .map(|(label, duration)| {
-                            format!("{}: {:?}", label, duration / args.repeat)
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ")
+    let format = match args.format {
+        Format::Text if args.batch => Format::Table,
+        format => format,
+    };
+    let mut reporter = Reporter::new(format, std::io::stdout().lock());
+    let mut total_duration = Duration::default();
+    let mut cpu_time = Duration::default();
+    let start = Instant::now();
+    let mut trace = args.trace.as_ref().map(|_| Trace::new(start));
+    run_ordered(
+        runs.len(),
+        jobs,
+        |i| {
+            let run = &runs[i];
+            if let Some(path) = &run.path {
+                let (diagnostics, result) = solve_in_child(run, path, &args);
+                return (diagnostics, result.map(|report| (report, None)));
+            }
+            let input = match &run.input {
+                Ok(input) => input,
+                Err(e) => return (String::new(), Err(e.clone())),
+            };
+            let cpu_start = thread_cpu_time();
+            let result = execute(
+                run.solver,
+                input,
+                &run.params,
+                args.day.part,
+                args.warmup,
+                args.repeat,
+            )
+            .map(|(solution, ctx)| {
+                let cpu_time = thread_cpu_time().saturating_sub(cpu_start);
+                (
+                    new_report(run, solution, &ctx, cpu_time, &args, answers.as_ref()),
+                    Some(ctx),
                 )
+            })
+            .map_err(|e| e.to_string());
+            (String::new(), result)
+        },
+        |i, (diagnostics, result)| {
+            let name = &runs[i].name;
+            eprint!("{}", diagnostics);
+            let (mut report, ctx) = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("{}: {}", name, e);
+                    errors += 1;
+                    return;
+                }
+            };
+            verifications.extend(report.parts.iter().filter_map(|p| p.verification.clone()));
+            if let Some(baseline) = &baseline {
+                report.baseline = baseline.compare(&report, args.regression_threshold);
+                regressions += report.baseline.iter().filter(|c| c.regression).count();
+            }
+            if let Some(saved_baseline) = &mut saved_baseline {
+                saved_baseline.record(&report);
+            }
+            if let (Some(trace), Some(ctx)) = (&mut trace, &ctx) {
+                trace.record(i, name, ctx);
             }
+            reporter.report(&report).unwrap();
+            total_duration += report.duration.mean;
+            cpu_time += report.cpu_time;
+        },
+    );
+    let totals = Totals {
+        duration: total_duration,
+        cpu_time,
+        wall: start.elapsed(),
+    };
+    reporter.finish((run_count > 1).then_some(totals)).unwrap();
+
+    // Batch runs read the parameters in their child processes, which warn themselves.
+    for (key, _) in args.params.iter().filter(|_| !args.batch) {
+        if runs
+            .iter()
+            .all(|run| run.params.unused().any(|unused| unused == key))
+        {
+            eprintln!("Warning: parameter {} is not used by any solver", key);
+        }
+    }
+
+    if let (Some(path), Some(trace)) = (&args.trace, &trace)
+        && let Err(e) = trace.save(path)
+    {
+        eprintln!("Unable to write trace to {}: {}", path, e);
+        return ExitCode::FAILURE;
+    }
+
+    if let (Some(name), Some(saved_baseline)) = (&args.save_baseline, &saved_baseline)
+        && let Err(e) = saved_baseline.save(name)
+    {
+        eprintln!("Unable to save baseline {}: {}", name, e);
+        return ExitCode::FAILURE;
+    }
+
+    let mut success = true;
+    if errors > 0 {
+        eprintln!("Errors: {} solvers failed", errors);
+        success = false;
+    }
+    if baseline.is_some() && regressions > 0 {
+        eprintln!("Baseline: {} regressions", regressions);
+        success = false;
+    }
+
+    if !verifications.is_empty() {
+        let count = |f: fn(&Verification) -> bool| verifications.iter().filter(|v| f(v)).count();
+        let failed = count(|v| matches!(v, Verification::Fail(_)));
+        eprintln!(
+            "Verification: {} passed, {} failed, {} missing",
+            count(|v| *v == Verification::Pass),
+            failed,
+            count(|v| *v == Verification::Missing),
         );
-        total_duration += per_iter_duration;
+        if failed > 0 {
+            success = false;
+        }
     }
 
-    if args.day.is_none() {
-        println!("Total time: {:?}", total_duration);
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
 }
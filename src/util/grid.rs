@@ -3,7 +3,7 @@ use num::integer::div_rem;
 use std::fmt::{Display, Formatter, Write};
 use std::ops::{Index, IndexMut};
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Grid<T> {
     pub dimensions: Dimensions,
     data: Vec<T>,
@@ -112,9 +112,7 @@ impl<T> Grid<T> {
         self.data[idx] = value;
     }
 
-    pub fn rows(
-        &self,
-    ) -> impl Iterator<Item = &[T]> + DoubleEndedIterator + ExactSizeIterator + '_ {
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
         self.data.chunks_exact(self.dimensions.1)
     }
 
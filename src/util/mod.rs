@@ -1,3 +1,4 @@
 pub mod grid;
+pub mod ocr;
 pub mod position;
 pub mod solver;
@@ -0,0 +1,120 @@
+use crate::util::grid::Grid;
+
+/// Letters of the 6 pixel high font, letters are separated by at least one empty column.
+#[rustfmt::skip]
+const SMALL: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the 10 pixel high font.
+#[rustfmt::skip]
+const LARGE: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Reads the block letters drawn on the grid, using the 6 or 10 pixel high font depending on
+/// the height of the drawing. Returns `None` when a letter is not recognized.
+pub fn ocr(grid: &Grid<bool>) -> Option<String> {
+    let rows = grid.rows().collect::<Vec<_>>();
+    let drawn = |row: &&[bool]| row.contains(&true);
+    let top = rows.iter().position(drawn)?;
+    let bottom = rows.iter().rposition(drawn)?;
+    let rows = &rows[top..=bottom];
+    match rows.len() {
+        6 => read(rows, SMALL),
+        10 => read(rows, LARGE),
+        _ => None,
+    }
+}
+
+fn read<const HEIGHT: usize>(rows: &[&[bool]], font: &[(char, [&str; HEIGHT])]) -> Option<String> {
+    let width = rows[0].len();
+    let empty = |x: usize| x >= width || rows.iter().all(|row| !row[x]);
+
+    let mut result = String::new();
+    let mut x = 0;
+    while x < width {
+        if empty(x) {
+            x += 1;
+            continue;
+        }
+        let (letter, glyph_width) = font.iter().find_map(|(letter, glyph)| {
+            let glyph_width = glyph[0].len();
+            let matches = rows.iter().zip(glyph).all(|(row, line)| {
+                line.bytes()
+                    .enumerate()
+                    .all(|(i, pixel)| row.get(x + i).copied().unwrap_or(false) == (pixel == b'#'))
+            });
+            (matches && empty(x + glyph_width)).then_some((*letter, glyph_width))
+        })?;
+        result.push(letter);
+        x += glyph_width;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render<const HEIGHT: usize>(font: &[(char, [&str; HEIGHT])], text: &str) -> Grid<bool> {
+        Grid::from_rows((0..HEIGHT).map(|y| {
+            text.chars()
+                .flat_map(|c| {
+                    let (_, glyph) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                    glyph[y].bytes().map(|pixel| pixel == b'#').chain([false])
+                })
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    #[test]
+    fn small() {
+        let text = SMALL.iter().map(|(letter, _)| letter).collect::<String>();
+        assert_eq!(ocr(&render(SMALL, &text)), Some(text));
+    }
+
+    #[test]
+    fn large() {
+        let text = LARGE.iter().map(|(letter, _)| letter).collect::<String>();
+        assert_eq!(ocr(&render(LARGE, &text)), Some(text));
+    }
+
+    #[test]
+    fn unknown() {
+        let mut grid = render(SMALL, "HI");
+        grid[0][1] = true;
+        assert_eq!(ocr(&grid), None);
+    }
+}